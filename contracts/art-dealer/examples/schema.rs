@@ -3,7 +3,14 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use art_dealer::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use art_dealer::msg::{
+    ApprovalHistoryResponse, ApprovalResponse, ApprovalsResponse, ArtMinterResponse,
+    ArtProvenanceResponse, CollectionsResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
+    ListingResponse, ListingsResponse, MigrateMsg, OffersResponse, QueryMsg, ReceiveNftMsg,
+    RoyaltiesInfoResponse, SaleOrderResponse, SaleOrdersResponse, VoucherRedeemedResponse,
+    VoucherSignerResponse,
+};
+use art_dealer::state::SaleConfig;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -14,6 +21,23 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(ApprovalResponse), &out_dir);
+    export_schema(&schema_for!(ApprovalsResponse), &out_dir);
+    export_schema(&schema_for!(ApprovalHistoryResponse), &out_dir);
+    export_schema(&schema_for!(ArtMinterResponse), &out_dir);
+    export_schema(&schema_for!(SaleConfig), &out_dir);
+    export_schema(&schema_for!(ListingResponse), &out_dir);
+    export_schema(&schema_for!(ListingsResponse), &out_dir);
+    export_schema(&schema_for!(ReceiveNftMsg), &out_dir);
+    export_schema(&schema_for!(SaleOrderResponse), &out_dir);
+    export_schema(&schema_for!(SaleOrdersResponse), &out_dir);
+    export_schema(&schema_for!(OffersResponse), &out_dir);
+    export_schema(&schema_for!(RoyaltiesInfoResponse), &out_dir);
+    export_schema(&schema_for!(CollectionsResponse), &out_dir);
+    export_schema(&schema_for!(ArtProvenanceResponse), &out_dir);
+    export_schema(&schema_for!(VoucherSignerResponse), &out_dir);
+    export_schema(&schema_for!(VoucherRedeemedResponse), &out_dir);
 }
@@ -1,27 +1,61 @@
 use crate::error::ContractError;
-use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Approval, Config, APPROVALS, CONFIG};
+use crate::msg::{
+    ApprovalHistoryResponse, ApprovalResponse, ApprovalsResponse, ArtMinterResponse,
+    ArtProvenanceResponse, CollectionsResponse, ConfigResponse, DaoApproval, DaoCollection,
+    ExecuteMsg, FeeRecipientMsg, InstantiateMsg, ListingResponse, ListingsResponse, MigrateMsg,
+    MintItem, MintVoucher, OffersResponse, QueryMsg, ReceiveNftMsg, RoyaltiesInfoResponse,
+    SaleOrderResponse, SaleOrdersResponse, VoucherRedeemedResponse, VoucherSignerResponse,
+};
+use crate::state::{
+    next_listing_id, record_approval_event, Approval, ApprovalAction, ArtMint, Config,
+    FeeRecipient, Listing, Offer, PendingCollection, Provenance, SaleConfig, SaleOrder, APPROVALS,
+    APPROVAL_HISTORY, COLLECTIONS, CONFIG, LISTINGS, OFFERS, PENDING_COLLECTION, REDEEMED_VOUCHERS,
+    SALE_CONFIG, SALE_ORDERS, TOKEN_COLLECTIONS, TOKEN_MINTERS, TOKEN_PROVENANCE, VOUCHER_SIGNERS,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdResult,
-    SubMsg, WasmMsg,
+    coins, from_binary, to_binary, to_vec, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut,
+    Env, Event, MessageInfo, Order, Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg,
+    Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw721::{Cw721ReceiveMsg, OwnerOfResponse};
 use cw721_metadata_onchain::{
-    ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg, Metadata,
+    msg::Cw2981QueryMsg, ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg,
+    Metadata, QueryMsg as Cw721QueryMsg,
 };
+use cw_storage_plus::Bound;
 use identityservice::msg::QueryMsg::GetIdentityByOwner;
 use identityservice::state::IdType::Dao;
+use identityservice::state::Identity;
 
-use cw_utils::parse_reply_instantiate_data;
+use cw_utils::{
+    ensure_from_older_version, must_pay, nonpayable, one_coin, parse_reply_instantiate_data,
+};
 use identityservice::msg::GetIdentityByOwnerResponse;
+use sha2::{Digest, Sha256};
+
+use crate::upgrades;
 
 // version info for migration info
-const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_ART_NFT_REPLY_ID: u64 = 1;
+const INSTANTIATE_COLLECTION_REPLY_ID: u64 = 2;
+
+// limits for art NFT metadata
+const MAX_METADATA_BYTES: usize = 16 * 1024;
+const MAX_ATTRIBUTES: usize = 64;
+const URI_SCHEMES: [&str; 3] = ["ipfs://", "ar://", "https://"];
+
+// Maximum number of art NFTs minted by a single BatchMintArt
+const MAX_BATCH_SIZE: usize = 50;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -32,12 +66,23 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    if msg.royalty_percentage > 100 {
+        return Err(ContractError::InvalidRoyaltyPercentage {});
+    }
+
     let config = Config {
         art_nft_address: None,
-        owner: msg.owner,
+        governance: msg.governance,
+        admin: msg.admin,
+        pauser: msg.pauser,
+        paused: false,
         identityservice_contract: msg.identityservice_contract,
         art_nft_name: msg.art_nft_name.clone(),
         art_nft_symbol: msg.art_nft_symbol.clone(),
+        reclaim_quota_on_burn: msg.reclaim_quota_on_burn,
+        royalty_percentage: msg.royalty_percentage,
+        art_nft_code_id: msg.art_nft_code_id,
+        collection_per_dao: msg.collection_per_dao,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -63,10 +108,30 @@ pub fn instantiate(
     Ok(Response::new().add_submessages(sub_msg))
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = get_contract_version(deps.storage)?.version;
+
+    // Upgrade the state release by release, every upgrade asserts the version it starts from
+    // and bumps the stored version to the release it migrates to
+    if stored_version == upgrades::v0_15::FROM_VERSION {
+        upgrades::v0_15::migrate(deps.branch())?;
+    }
+
+    // Make sure the correct contract is being upgraded and that it's not a downgrade
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored_version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         INSTANTIATE_ART_NFT_REPLY_ID => instantiate_art_nft_reply(deps, msg),
+        INSTANTIATE_COLLECTION_REPLY_ID => instantiate_collection_reply(deps, msg),
         _ => Err(ContractError::InvalidTokenReplyId {}),
     }
 }
@@ -85,10 +150,92 @@ fn instantiate_art_nft_reply(deps: DepsMut, msg: Reply) -> Result<Response, Cont
     Ok(Response::new())
 }
 
+// Links the new collection to its DAO and sends the mints that were waiting for it
+fn instantiate_collection_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let pending = PENDING_COLLECTION.load(deps.storage)?;
+    PENDING_COLLECTION.remove(deps.storage);
+
+    let reply = parse_reply_instantiate_data(msg).unwrap();
+    let collection = Addr::unchecked(reply.contract_address);
+    COLLECTIONS.save(deps.storage, &pending.dao, &collection)?;
+
+    let mint_msgs = pending
+        .mints
+        .into_iter()
+        .map(|mint| {
+            TOKEN_COLLECTIONS.save(deps.storage, &mint.token_id, &collection)?;
+            Ok(WasmMsg::Execute {
+                contract_addr: collection.to_string(),
+                msg: mint.msg,
+                funds: vec![],
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(mint_msgs)
+        .add_attribute("action", "create_collection")
+        .add_attribute("dao", pending.dao)
+        .add_attribute("collection", collection))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetConfig {} => to_binary(&query_config(deps)?),
+        QueryMsg::Approval { dao } => to_binary(&query_approval(deps, dao)?),
+        QueryMsg::Approvals {
+            start_after,
+            limit,
+            active_only,
+        } => to_binary(&query_approvals(
+            deps,
+            env,
+            start_after,
+            limit,
+            active_only.unwrap_or(false),
+        )?),
+        QueryMsg::ApprovalHistory {
+            dao,
+            start_after,
+            limit,
+        } => to_binary(&query_approval_history(deps, dao, start_after, limit)?),
+        QueryMsg::ArtMinter { token_id } => to_binary(&query_art_minter(deps, token_id)?),
+        QueryMsg::SaleConfig {} => {
+            to_binary(&SALE_CONFIG.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::Listing { listing_id } => to_binary(&query_listing(deps, listing_id)?),
+        QueryMsg::Listings { start_after, limit } => {
+            to_binary(&query_listings(deps, start_after, limit)?)
+        }
+        QueryMsg::SaleOrder { token_id } => to_binary(&SaleOrderResponse {
+            sale_order: SALE_ORDERS.may_load(deps.storage, &token_id)?,
+        }),
+        QueryMsg::SaleOrders { start_after, limit } => {
+            to_binary(&query_sale_orders(deps, start_after, limit)?)
+        }
+        QueryMsg::Offers {
+            token_id,
+            start_after,
+            limit,
+        } => to_binary(&query_offers(deps, token_id, start_after, limit)?),
+        QueryMsg::RoyaltyInfo {
+            token_id,
+            sale_price,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&query_royalty_info(deps, &config, token_id, sale_price)?)
+        }
+        QueryMsg::ArtProvenance { token_id } => to_binary(&ArtProvenanceResponse {
+            provenance: TOKEN_PROVENANCE.may_load(deps.storage, &token_id)?,
+        }),
+        QueryMsg::Collections { start_after, limit } => {
+            to_binary(&query_collections(deps, start_after, limit)?)
+        }
+        QueryMsg::VoucherSigner { dao } => to_binary(&query_voucher_signer(deps, dao)?),
+        QueryMsg::VoucherRedeemed { dao, token_id } => {
+            to_binary(&query_voucher_redeemed(deps, dao, token_id)?)
+        }
     }
 }
 
@@ -96,14 +243,211 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     println!("config {:?}", config);
     Ok(ConfigResponse {
-        owner: config.owner,
+        governance: config.governance,
+        admin: config.admin,
+        pauser: config.pauser,
+        paused: config.paused,
         identityservice_contract: config.identityservice_contract,
         art_nft_address: config.art_nft_address,
         art_nft_name: config.art_nft_name,
         art_nft_symbol: config.art_nft_symbol,
+        reclaim_quota_on_burn: config.reclaim_quota_on_burn,
+        royalty_percentage: config.royalty_percentage,
+        art_nft_code_id: config.art_nft_code_id,
+        collection_per_dao: config.collection_per_dao,
     })
 }
 
+fn query_approval(deps: Deps, dao: String) -> StdResult<ApprovalResponse> {
+    let dao = deps.api.addr_validate(&dao)?;
+    let approval = APPROVALS.may_load(deps.storage, &dao)?;
+
+    Ok(ApprovalResponse { approval })
+}
+
+fn query_approvals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    active_only: bool,
+) -> StdResult<ApprovalsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|dao| deps.api.addr_validate(&dao))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let approvals = APPROVALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, approval)) => {
+                !active_only || approval.is_active(env.block.height, config.reclaim_quota_on_burn)
+            }
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(dao, approval)| DaoApproval { dao, approval }))
+        .collect::<StdResult<_>>()?;
+
+    Ok(ApprovalsResponse { approvals })
+}
+
+fn query_approval_history(
+    deps: Deps,
+    dao: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ApprovalHistoryResponse> {
+    let dao = deps.api.addr_validate(&dao)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let events = APPROVAL_HISTORY
+        .prefix(&dao)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, event)| event))
+        .collect::<StdResult<_>>()?;
+
+    Ok(ApprovalHistoryResponse { events })
+}
+
+fn query_art_minter(deps: Deps, token_id: String) -> StdResult<ArtMinterResponse> {
+    let dao = TOKEN_MINTERS.may_load(deps.storage, &token_id)?;
+
+    Ok(ArtMinterResponse { dao })
+}
+
+fn query_listing(deps: Deps, listing_id: u64) -> StdResult<ListingResponse> {
+    let listing = LISTINGS.may_load(deps.storage, listing_id)?;
+
+    Ok(ListingResponse { listing })
+}
+
+fn query_listings(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ListingsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let listings = LISTINGS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, listing)| listing))
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListingsResponse { listings })
+}
+
+fn query_sale_orders(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SaleOrdersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let sale_orders = SALE_ORDERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, sale_order)| sale_order))
+        .collect::<StdResult<_>>()?;
+
+    Ok(SaleOrdersResponse { sale_orders })
+}
+
+fn query_offers(
+    deps: Deps,
+    token_id: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|buyer| deps.api.addr_validate(&buyer))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let offers = OFFERS
+        .prefix(&token_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, offer)| offer))
+        .collect::<StdResult<_>>()?;
+
+    Ok(OffersResponse { offers })
+}
+
+// Asks the collection of the art NFT for its cw2981 royalty, tokens without one owe no royalty
+fn query_royalty_info(
+    deps: Deps,
+    config: &Config,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<RoyaltiesInfoResponse> {
+    let collection = token_collection(deps.storage, config, &token_id)?;
+
+    // The shared art NFT of migrated deployments may still run cw721 code without cw2981
+    // queries, its art NFTs owe no royalty
+    Ok(deps
+        .querier
+        .query_wasm_smart(
+            collection,
+            &Cw721QueryMsg::Extension {
+                msg: Cw2981QueryMsg::RoyaltyInfo {
+                    token_id,
+                    sale_price,
+                },
+            },
+        )
+        .unwrap_or(RoyaltiesInfoResponse {
+            address: String::new(),
+            royalty_amount: Uint128::zero(),
+        }))
+}
+
+fn query_collections(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CollectionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|dao| deps.api.addr_validate(&dao))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let collections = COLLECTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(dao, collection)| DaoCollection { dao, collection }))
+        .collect::<StdResult<_>>()?;
+
+    Ok(CollectionsResponse { collections })
+}
+
+fn query_voucher_signer(deps: Deps, dao: String) -> StdResult<VoucherSignerResponse> {
+    let dao = deps.api.addr_validate(&dao)?;
+    let pubkey = VOUCHER_SIGNERS.may_load(deps.storage, &dao)?;
+
+    Ok(VoucherSignerResponse { pubkey })
+}
+
+fn query_voucher_redeemed(
+    deps: Deps,
+    dao: String,
+    token_id: String,
+) -> StdResult<VoucherRedeemedResponse> {
+    let dao = deps.api.addr_validate(&dao)?;
+    let redeemed_at = REDEEMED_VOUCHERS.may_load(deps.storage, (&dao, token_id.as_str()))?;
+
+    Ok(VoucherRedeemedResponse { redeemed_at })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -118,13 +462,111 @@ pub fn execute(
             token_uri,
             metadata,
         } => execute_mint_art(deps, env, info, token_id, owner, token_uri, metadata),
+        ExecuteMsg::BatchMintArt { items } => execute_batch_mint_art(deps, env, info, items),
+        ExecuteMsg::SetVoucherSigner { pubkey } => {
+            execute_set_voucher_signer(deps, env, info, pubkey)
+        }
+        ExecuteMsg::RedeemVoucher { voucher, signature } => {
+            execute_redeem_voucher(deps, env, info, voucher, signature)
+        }
+        ExecuteMsg::CreateCollection { name, symbol } => {
+            execute_create_collection(deps, env, info, name, symbol)
+        }
+        ExecuteMsg::UpdateCollectionConfig {
+            art_nft_code_id,
+            collection_per_dao,
+        } => execute_update_collection_config(deps, env, info, art_nft_code_id, collection_per_dao),
         ExecuteMsg::ApproveDealer {
             dao,
             approved, // amount of NFTs to be minted
             duration, // in blocks
-        } => execute_approve_dealer(deps, env, info, dao, approved, duration),
+            proposal_id,
+        } => execute_approve_dealer(deps, env, info, dao, approved, duration, proposal_id),
         ExecuteMsg::RevokeDealer { dao } => execute_revoke_dealer(deps, env, info, dao),
+        ExecuteMsg::UpdateRoles {
+            governance,
+            admin,
+            pauser,
+        } => execute_update_roles(deps, env, info, governance, admin, pauser),
+        ExecuteMsg::Pause {} => execute_set_paused(deps, env, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, env, info, false),
+        ExecuteMsg::BurnArt { token_id } => execute_burn_art(deps, env, info, token_id),
+        ExecuteMsg::ListArt {
+            token_id,
+            token_uri,
+            metadata,
+            price,
+        } => execute_list_art(deps, env, info, token_id, token_uri, metadata, price),
+        ExecuteMsg::CancelListing { listing_id } => {
+            execute_cancel_listing(deps, env, info, listing_id)
+        }
+        ExecuteMsg::Purchase { listing_id } => execute_purchase(deps, env, info, listing_id),
+        ExecuteMsg::UpdateSaleConfig {
+            denom,
+            treasury_share,
+            fee_recipients,
+        } => execute_update_sale_config(deps, env, info, denom, treasury_share, fee_recipients),
+        ExecuteMsg::ReceiveNft(receive_msg) => execute_receive_nft(deps, env, info, receive_msg),
+        ExecuteMsg::Buy { token_id } => execute_buy(deps, env, info, token_id),
+        ExecuteMsg::CancelSale { token_id } => execute_cancel_sale(deps, env, info, token_id),
+        ExecuteMsg::MakeOffer { token_id } => execute_make_offer(deps, env, info, token_id),
+        ExecuteMsg::CancelOffer { token_id } => execute_cancel_offer(deps, env, info, token_id),
+        ExecuteMsg::AcceptOffer { token_id, buyer } => {
+            execute_accept_offer(deps, env, info, token_id, buyer)
+        }
+    }
+}
+
+pub fn execute_create_collection(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: Option<String>,
+    symbol: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_paused(&config)?;
+    assert_is_dao(deps.as_ref(), &config, &info.sender)?;
+
+    if COLLECTIONS.has(deps.storage, &info.sender) {
+        return Err(ContractError::CollectionExists {});
+    }
+
+    let instantiate_msg =
+        instantiate_collection_msg(deps.as_ref(), &env, &config, &info.sender, name, symbol)?;
+    PENDING_COLLECTION.save(
+        deps.storage,
+        &PendingCollection {
+            dao: info.sender,
+            mints: vec![],
+        },
+    )?;
+
+    Ok(Response::new().add_submessage(instantiate_msg))
+}
+
+pub fn execute_update_collection_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    art_nft_code_id: Option<u64>,
+    collection_per_dao: Option<bool>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if config.governance != info.sender && config.admin.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
+
+    if let Some(art_nft_code_id) = art_nft_code_id {
+        config.art_nft_code_id = art_nft_code_id;
+    }
+    if let Some(collection_per_dao) = collection_per_dao {
+        config.collection_per_dao = collection_per_dao;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_collection_config"))
 }
 
 pub fn execute_approve_dealer(
@@ -134,44 +576,122 @@ pub fn execute_approve_dealer(
     dao: Addr,
     approved: u64, // amount of NFTs to be minted
     duration: u64, // in blocks
+    proposal_id: Option<u64>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    if config.owner != info.sender {
+    // Approvals are only decided through governance proposals
+    if config.governance != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
     let expires = env.block.height + duration;
+    let id = record_approval_event(
+        deps.storage,
+        &dao,
+        ApprovalAction::Approve {
+            approved,
+            expires,
+            proposal_id,
+        },
+        env.block.height,
+    )?;
     let approval = Approval {
         approved,
         minted: 0,
         burned: 0,
         expires,
+        proposal_id,
+        id,
     };
     APPROVALS.save(deps.storage, &dao, &approval)?;
+
     Ok(Response::new()
         .add_attribute("block_time", env.block.time.to_string())
         .add_attribute("expires", expires.to_string())
-        .add_attribute("owner", config.owner)
+        .add_attribute("governance", config.governance)
         .add_attribute("sender", info.sender))
 }
 
 pub fn execute_revoke_dealer(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     dao: Addr,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    if config.owner != info.sender {
-        return Err(ContractError::UnauthorizedTokenContract {}); // TODO: change error
+    // A DAO can give up its own approval
+    if config.governance != info.sender && dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Only an existing approval can be revoked, so the history doesn't record empty revokes
+    if !APPROVALS.has(deps.storage, &dao) {
+        return Err(ContractError::DealerNotApproved {});
     }
 
     APPROVALS.remove(deps.storage, &dao);
+    record_approval_event(
+        deps.storage,
+        &dao,
+        ApprovalAction::Revoke {},
+        env.block.height,
+    )?;
+
     Ok(Response::new())
 }
 
+pub fn execute_update_roles(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    governance: Option<String>,
+    admin: Option<String>,
+    pauser: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    let is_governance = config.governance == info.sender;
+    let is_admin = config.admin.as_ref() == Some(&info.sender);
+    if !is_governance && !(is_admin && governance.is_none()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(governance) = governance {
+        config.governance = deps.api.addr_validate(&governance)?;
+    }
+    if let Some(admin) = admin {
+        config.admin = Some(deps.api.addr_validate(&admin)?);
+    }
+    if let Some(pauser) = pauser {
+        config.pauser = Some(deps.api.addr_validate(&pauser)?);
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_roles"))
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if config.governance != info.sender && config.pauser.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.paused = paused;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", if paused { "pause" } else { "unpause" })
+        .add_attribute("sender", info.sender))
+}
+
 pub fn execute_mint_art(
     deps: DepsMut,
     env: Env,
@@ -182,37 +702,257 @@ pub fn execute_mint_art(
     metadata: Option<Metadata>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let art_nft_address = config.art_nft_address.clone().unwrap();
+    assert_nft_contracts_are_linked(&config)?;
+    assert_is_dao(deps.as_ref(), &config, &info.sender)?;
+
+    let mint_msgs = mint_art(
+        deps,
+        &env,
+        &config,
+        &info.sender,
+        token_id,
+        owner,
+        token_uri,
+        metadata,
+    )?;
 
-    assert_nft_contracts_are_linked(&config).unwrap();
+    Ok(Response::new().add_submessages(mint_msgs))
+}
 
-    // Ensure info.sender is a DAO
-    let maybe_identity_resp: GetIdentityByOwnerResponse = deps.querier.query_wasm_smart(
-        config.identityservice_contract.clone(),
-        &GetIdentityByOwner {
-            owner: info.sender.clone().into(),
-        },
+pub fn execute_batch_mint_art(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    items: Vec<MintItem>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_paused(&config)?;
+    assert_nft_contracts_are_linked(&config)?;
+    assert_is_dao(deps.as_ref(), &config, &info.sender)?;
+
+    if items.len() > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge {
+            max: MAX_BATCH_SIZE,
+        });
+    }
+
+    // Either the whole batch fits into the remaining quota or nothing is minted
+    use_approval(
+        deps.storage,
+        &env,
+        &config,
+        &info.sender,
+        items.len() as u64,
     )?;
 
-    let maybe_identity = maybe_identity_resp.identity;
+    let count = items.len();
+    let mints = items
+        .into_iter()
+        .map(|item| {
+            art_mint_msg(
+                deps.storage,
+                &env,
+                &config,
+                &info.sender,
+                item.token_id,
+                item.owner,
+                item.token_uri,
+                item.metadata,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mint_msgs = mint_to_collection(deps, &env, &config, &info.sender, mints)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "batch_mint_art")
+        .add_attribute("dao", info.sender)
+        .add_attribute("count", count.to_string())
+        .add_submessages(mint_msgs))
+}
+
+pub fn execute_set_voucher_signer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pubkey: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_is_dao(deps.as_ref(), &config, &info.sender)?;
+
+    match pubkey {
+        Some(pubkey) => VOUCHER_SIGNERS.save(deps.storage, &info.sender, &pubkey)?,
+        None => VOUCHER_SIGNERS.remove(deps.storage, &info.sender),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_voucher_signer")
+        .add_attribute("dao", info.sender))
+}
+
+pub fn execute_redeem_voucher(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    voucher: MintVoucher,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_nft_contracts_are_linked(&config)?;
+
+    let dao = deps.api.addr_validate(&voucher.dao)?;
+    if env.block.height > voucher.expires {
+        return Err(ContractError::VoucherExpired {});
+    }
+    if REDEEMED_VOUCHERS.has(deps.storage, (&dao, voucher.token_id.as_str())) {
+        return Err(ContractError::VoucherAlreadyRedeemed {
+            token_id: voucher.token_id,
+        });
+    }
+
+    let pubkey = VOUCHER_SIGNERS
+        .may_load(deps.storage, &dao)?
+        .ok_or(ContractError::VoucherSignerNotSet {})?;
+    verify_voucher(deps.as_ref(), &env, &voucher, &signature, &pubkey)?;
+
+    if let Some(buyer) = &voucher.buyer {
+        if *buyer != info.sender {
+            return Err(ContractError::VoucherBuyerMismatch {});
+        }
+    }
 
-    match maybe_identity {
-        Some(identity) => {
-            if identity.id_type != Dao {
-                return Err(ContractError::Unauthorized {});
+    let paid = match &voucher.price {
+        Some(price) => {
+            let paid = must_pay(&info, &price.denom)?;
+            if paid != price.amount {
+                return Err(ContractError::WrongPaymentAmount {});
             }
+            paid
         }
-        None => return Err(ContractError::Unauthorized {}),
+        None => {
+            nonpayable(&info)?;
+            Uint128::zero()
+        }
+    };
+
+    REDEEMED_VOUCHERS.save(
+        deps.storage,
+        (&dao, voucher.token_id.as_str()),
+        &env.block.height,
+    )?;
+
+    let mint_msgs = mint_art(
+        deps.branch(),
+        &env,
+        &config,
+        &dao,
+        voucher.token_id.clone(),
+        info.sender.to_string(),
+        voucher.token_uri,
+        voucher.metadata,
+    )?;
+
+    let mut response = Response::new().add_submessages(mint_msgs).add_event(
+        Event::new("art_voucher_redeemed")
+            .add_attribute("token_id", voucher.token_id.clone())
+            .add_attribute("dao", dao.clone())
+            .add_attribute("buyer", info.sender),
+    );
+
+    if let Some(price) = voucher.price {
+        let splits = sale_splits(deps.storage, &config, &dao, paid)?;
+        response = add_sale_splits(
+            response,
+            splits,
+            &price.denom,
+            ("token_id", voucher.token_id),
+        );
     }
 
-    let maybe_approval = APPROVALS.may_load(deps.storage, &info.sender)?;
+    Ok(response)
+}
 
-    // Assert sender has art dealer approval
-    if maybe_approval.is_none() {
-        return Err(ContractError::DealerNotApproved {});
+// The DAO signs the sha256 hash of the art-dealer address followed by the JSON encoded voucher
+// (see MintVoucher), so a voucher can't be replayed on another art-dealer instance
+fn verify_voucher(
+    deps: Deps,
+    env: &Env,
+    voucher: &MintVoucher,
+    signature: &Binary,
+    pubkey: &Binary,
+) -> Result<(), ContractError> {
+    let mut message = env.contract.address.as_bytes().to_vec();
+    message.extend(to_vec(voucher)?);
+    let hash = Sha256::digest(&message);
+
+    let valid = deps
+        .api
+        .secp256k1_verify(&hash, signature, pubkey)
+        .map_err(|_| ContractError::InvalidVoucherSignature {})?;
+    if !valid {
+        return Err(ContractError::InvalidVoucherSignature {});
+    }
+    Ok(())
+}
+
+// Ensure the address is a DAO
+fn assert_is_dao(deps: Deps, config: &Config, addr: &Addr) -> Result<(), ContractError> {
+    dao_identity(deps, config, addr).map(|_| ())
+}
+
+fn dao_identity(deps: Deps, config: &Config, addr: &Addr) -> Result<Identity, ContractError> {
+    let maybe_identity_resp: GetIdentityByOwnerResponse = deps.querier.query_wasm_smart(
+        config.identityservice_contract.clone(),
+        &GetIdentityByOwner {
+            owner: addr.to_string(),
+        },
+    )?;
+
+    match maybe_identity_resp.identity {
+        Some(identity) if identity.id_type == Dao => Ok(identity),
+        _ => Err(ContractError::Unauthorized {}),
     }
+}
+
+// Counts the mint against the approval of the DAO and returns the msgs minting the art NFT
+#[allow(clippy::too_many_arguments)]
+fn mint_art(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    dao: &Addr,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    metadata: Option<Metadata>,
+) -> Result<Vec<SubMsg>, ContractError> {
+    assert_not_paused(config)?;
+    use_approval(deps.storage, env, config, dao, 1)?;
+
+    let mint = art_mint_msg(
+        deps.storage,
+        env,
+        config,
+        dao,
+        token_id,
+        owner,
+        token_uri,
+        metadata,
+    )?;
+    mint_to_collection(deps, env, config, dao, vec![mint])
+}
 
-    let mut approval = maybe_approval.unwrap();
+// Counts the amount of NFTs against the approval of the DAO
+fn use_approval(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    dao: &Addr,
+    amount: u64,
+) -> Result<(), ContractError> {
+    // Assert dao has art dealer approval
+    let mut approval = APPROVALS
+        .may_load(storage, dao)?
+        .ok_or(ContractError::DealerNotApproved {})?;
 
     // Assert approval is not expired
     if env.block.height > approval.expires {
@@ -220,27 +960,799 @@ pub fn execute_mint_art(
     }
 
     // Assert approved amount is not exceeded
-    if approval.minted + 1 > approval.approved {
+    if approval.used(config.reclaim_quota_on_burn) + amount > approval.approved {
         return Err(ContractError::ApprovedExceeded {});
     }
 
-    approval.minted += 1;
-    APPROVALS.save(deps.storage, &info.sender, &approval)?;
+    approval.minted += amount;
+    APPROVALS.save(storage, dao, &approval)?;
+    Ok(())
+}
+
+// Records the DAO as minter of the art NFT and returns the mint msg for its collection,
+// the mint has to be counted against the approval already
+#[allow(clippy::too_many_arguments)]
+fn art_mint_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    dao: &Addr,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    metadata: Option<Metadata>,
+) -> Result<ArtMint, ContractError> {
+    // Token ids are unique across all collections, so trades can refer to them by token id
+    if TOKEN_MINTERS.has(storage, &token_id) {
+        return Err(ContractError::TokenIdTaken { token_id });
+    }
+
+    validate_art(&token_uri, &metadata)?;
+    let metadata = with_royalty(config, dao, metadata)?;
+
+    let approval = APPROVALS.load(storage, dao)?;
+    TOKEN_MINTERS.save(storage, &token_id, dao)?;
+    TOKEN_PROVENANCE.save(
+        storage,
+        &token_id,
+        &Provenance {
+            dao: dao.clone(),
+            height: env.block.height,
+            proposal_id: approval.proposal_id,
+            content_hash: metadata
+                .as_ref()
+                .and_then(|metadata| metadata.content_hash.clone()),
+            approval_id: approval.id,
+        },
+    )?;
+    record_approval_event(
+        storage,
+        dao,
+        ApprovalAction::Mint {
+            token_id: token_id.clone(),
+        },
+        env.block.height,
+    )?;
 
     let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id,
+        token_id: token_id.clone(),
         owner,
         token_uri,
         extension: metadata,
     };
 
-    let callback = WasmMsg::Execute {
-        contract_addr: art_nft_address.to_string(),
+    Ok(ArtMint {
+        token_id,
         msg: to_binary(&mint_msg)?,
+    })
+}
+
+// Sends the mints to the collection of the DAO. Without one they go to art_nft_address, unless
+// every DAO gets its own collection, then it is created first and the mints are sent on reply
+fn mint_to_collection(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    dao: &Addr,
+    mints: Vec<ArtMint>,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let collection = match COLLECTIONS.may_load(deps.storage, dao)? {
+        Some(collection) => {
+            for mint in &mints {
+                TOKEN_COLLECTIONS.save(deps.storage, &mint.token_id, &collection)?;
+            }
+            collection
+        }
+        None if config.collection_per_dao => {
+            let instantiate_msg =
+                instantiate_collection_msg(deps.as_ref(), env, config, dao, None, None)?;
+            PENDING_COLLECTION.save(
+                deps.storage,
+                &PendingCollection {
+                    dao: dao.clone(),
+                    mints,
+                },
+            )?;
+            return Ok(vec![instantiate_msg]);
+        }
+        None => config.art_nft_address.clone().unwrap(),
+    };
+
+    Ok(mints
+        .into_iter()
+        .map(|mint| {
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: collection.to_string(),
+                msg: mint.msg,
+                funds: vec![],
+            })
+        })
+        .collect())
+}
+
+fn instantiate_collection_msg(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    dao: &Addr,
+    name: Option<String>,
+    symbol: Option<String>,
+) -> Result<SubMsg, ContractError> {
+    if config.art_nft_code_id == 0 {
+        return Err(ContractError::CollectionsDisabled {});
+    }
+
+    let name = match name {
+        Some(name) => name,
+        None => dao_identity(deps, config, dao)?.name,
+    };
+
+    Ok(SubMsg {
+        msg: WasmMsg::Instantiate {
+            code_id: config.art_nft_code_id,
+            msg: to_binary(&Cw721InstantiateMsg {
+                name: name.clone(),
+                symbol: symbol.unwrap_or_else(|| config.art_nft_symbol.clone()),
+                minter: env.contract.address.to_string(),
+            })?,
+            funds: vec![],
+            admin: None,
+            label: format!("Art NFT {}", name),
+        }
+        .into(),
+        id: INSTANTIATE_COLLECTION_REPLY_ID,
+        gas_limit: None,
+        reply_on: ReplyOn::Success,
+    })
+}
+
+fn validate_art(
+    token_uri: &Option<String>,
+    metadata: &Option<Metadata>,
+) -> Result<(), ContractError> {
+    if let Some(token_uri) = token_uri {
+        validate_uri(token_uri)?;
+    }
+
+    let metadata = match metadata {
+        Some(metadata) => metadata,
+        None => return Ok(()),
+    };
+
+    if to_vec(metadata)?.len() > MAX_METADATA_BYTES {
+        return Err(ContractError::MetadataTooLarge {});
+    }
+    if let Some(attributes) = &metadata.attributes {
+        if attributes.len() > MAX_ATTRIBUTES {
+            return Err(ContractError::TooManyAttributes {});
+        }
+    }
+
+    let uris = [
+        &metadata.image,
+        &metadata.external_url,
+        &metadata.animation_url,
+        &metadata.youtube_url,
+    ];
+    for uri in uris.iter().filter_map(|uri| uri.as_deref()) {
+        validate_uri(uri)?;
+    }
+
+    // Hex encoded sha256 hash
+    if let Some(content_hash) = &metadata.content_hash {
+        if content_hash.len() != 64 || !content_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ContractError::InvalidContentHash {});
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_uri(uri: &str) -> Result<(), ContractError> {
+    if !URI_SCHEMES.iter().any(|scheme| uri.starts_with(scheme)) {
+        return Err(ContractError::InvalidUri {
+            uri: uri.to_string(),
+        });
+    }
+    Ok(())
+}
+
+// Art NFTs pay royalties on every sale, to the DAO that minted them unless the metadata
+// names another royalty payment address
+fn with_royalty(
+    config: &Config,
+    dao: &Addr,
+    metadata: Option<Metadata>,
+) -> Result<Option<Metadata>, ContractError> {
+    if metadata.is_none() && config.royalty_percentage == 0 {
+        return Ok(None);
+    }
+
+    let mut metadata = metadata.unwrap_or_default();
+    let royalty_percentage = metadata
+        .royalty_percentage
+        .unwrap_or(config.royalty_percentage);
+    if royalty_percentage > 100 {
+        return Err(ContractError::InvalidRoyaltyPercentage {});
+    }
+
+    // Art NFTs without a royalty leave the royalty fields out, cw721 code older than the
+    // royalties rejects them
+    if royalty_percentage == 0 {
+        metadata.royalty_percentage = None;
+        metadata.royalty_payment_address = None;
+        return Ok(Some(metadata));
+    }
+
+    metadata.royalty_percentage = Some(royalty_percentage);
+    if metadata.royalty_payment_address.is_none() {
+        metadata.royalty_payment_address = Some(dao.to_string());
+    }
+
+    Ok(Some(metadata))
+}
+
+pub fn execute_burn_art(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_nft_contracts_are_linked(&config)?;
+    let art_nft_address = token_collection(deps.storage, &config, &token_id)?;
+
+    // The NFT contract only checks that art-dealer may burn the token, not who asked for it
+    let owner_of: OwnerOfResponse = deps.querier.query_wasm_smart(
+        art_nft_address.clone(),
+        &Cw721QueryMsg::OwnerOf {
+            token_id: token_id.clone(),
+            include_expired: None,
+        },
+    )?;
+    if owner_of.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // NFTs minted before the minters were tracked are burned without accounting, NFTs minted
+    // under an earlier approval of the DAO don't free up the quota of its current one
+    if let Some(dao) = TOKEN_MINTERS.may_load(deps.storage, &token_id)? {
+        let approval_id = TOKEN_PROVENANCE
+            .may_load(deps.storage, &token_id)?
+            .map_or(0, |provenance| provenance.approval_id);
+        if let Some(mut approval) = APPROVALS.may_load(deps.storage, &dao)? {
+            if approval.id == approval_id {
+                approval.burned += 1;
+                APPROVALS.save(deps.storage, &dao, &approval)?;
+            }
+        }
+        record_approval_event(
+            deps.storage,
+            &dao,
+            ApprovalAction::Burn {
+                token_id: token_id.clone(),
+            },
+            env.block.height,
+        )?;
+    }
+
+    let burn_msg = WasmMsg::Execute {
+        contract_addr: art_nft_address.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Burn {
+            token_id: token_id.clone(),
+        })?,
         funds: vec![],
     };
 
-    Ok(Response::new().add_message(callback))
+    Ok(Response::new()
+        .add_message(burn_msg)
+        .add_attribute("action", "burn_art")
+        .add_attribute("token_id", token_id)
+        .add_attribute("owner", info.sender))
+}
+
+pub fn execute_update_sale_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: Option<String>,
+    treasury_share: Decimal,
+    fee_recipients: Vec<FeeRecipientMsg>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if config.governance != info.sender && config.admin.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let fee_recipients = fee_recipients
+        .into_iter()
+        .map(|recipient| -> StdResult<_> {
+            Ok(FeeRecipient {
+                address: deps.api.addr_validate(&recipient.address)?,
+                share: recipient.share,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // The shares can't add up to more than the price
+    let total_share = fee_recipients
+        .iter()
+        .try_fold(treasury_share, |total, recipient| {
+            total.checked_add(recipient.share)
+        })
+        .map_err(|_| ContractError::InvalidSaleShares {})?;
+    if total_share > Decimal::one() {
+        return Err(ContractError::InvalidSaleShares {});
+    }
+
+    SALE_CONFIG.save(
+        deps.storage,
+        &SaleConfig {
+            denom,
+            treasury_share,
+            fee_recipients,
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("action", "update_sale_config"))
+}
+
+pub fn execute_list_art(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    token_uri: Option<String>,
+    metadata: Option<Metadata>,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_paused(&config)?;
+    assert_nft_contracts_are_linked(&config)?;
+    assert_is_dao(deps.as_ref(), &config, &info.sender)?;
+
+    let denom = SALE_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .denom
+        .ok_or(ContractError::SalesDisabled {})?;
+
+    validate_art(&token_uri, &metadata)?;
+
+    // The approval is only counted on purchase, but listing requires an active one
+    let approval = APPROVALS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::DealerNotApproved {})?;
+    if env.block.height > approval.expires {
+        return Err(ContractError::ApprovalExpired {});
+    }
+
+    let listing = Listing {
+        id: next_listing_id(deps.storage)?,
+        dao: info.sender,
+        token_id,
+        token_uri,
+        metadata,
+        price: Coin {
+            denom,
+            amount: price,
+        },
+    };
+    LISTINGS.save(deps.storage, listing.id, &listing)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "list_art")
+        .add_attribute("listing_id", listing.id.to_string())
+        .add_attribute("dao", listing.dao)
+        .add_attribute("token_id", listing.token_id)
+        .add_attribute("price", listing.price.to_string()))
+}
+
+pub fn execute_cancel_listing(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let listing = LISTINGS
+        .may_load(deps.storage, listing_id)?
+        .ok_or(ContractError::ListingNotFound { listing_id })?;
+
+    if listing.dao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    LISTINGS.remove(deps.storage, listing_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_listing")
+        .add_attribute("listing_id", listing_id.to_string()))
+}
+
+pub fn execute_purchase(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    listing_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_nft_contracts_are_linked(&config)?;
+
+    let listing = LISTINGS
+        .may_load(deps.storage, listing_id)?
+        .ok_or(ContractError::ListingNotFound { listing_id })?;
+
+    let paid = must_pay(&info, &listing.price.denom)?;
+    if paid != listing.price.amount {
+        return Err(ContractError::WrongPaymentAmount {});
+    }
+
+    LISTINGS.remove(deps.storage, listing_id);
+
+    let mint_msgs = mint_art(
+        deps.branch(),
+        &env,
+        &config,
+        &listing.dao,
+        listing.token_id.clone(),
+        info.sender.to_string(),
+        listing.token_uri,
+        listing.metadata,
+    )?;
+
+    let splits = sale_splits(deps.storage, &config, &listing.dao, paid)?;
+
+    let response = Response::new().add_submessages(mint_msgs).add_event(
+        Event::new("art_sale")
+            .add_attribute("listing_id", listing_id.to_string())
+            .add_attribute("token_id", listing.token_id)
+            .add_attribute("dao", listing.dao)
+            .add_attribute("buyer", info.sender)
+            .add_attribute("price", listing.price.to_string()),
+    );
+
+    Ok(add_sale_splits(
+        response,
+        splits,
+        &listing.price.denom,
+        ("listing_id", listing_id.to_string()),
+    ))
+}
+
+// Fee recipients and the treasury get their share rounded down, the DAO gets the rest
+fn sale_splits(
+    storage: &dyn Storage,
+    config: &Config,
+    dao: &Addr,
+    paid: Uint128,
+) -> StdResult<Vec<(&'static str, Addr, Uint128)>> {
+    let sale_config = SALE_CONFIG.may_load(storage)?.unwrap_or_default();
+    let mut splits: Vec<(&str, Addr, Uint128)> = sale_config
+        .fee_recipients
+        .iter()
+        .map(|recipient| ("fee", recipient.address.clone(), paid * recipient.share))
+        .collect();
+    splits.push((
+        "treasury",
+        config.governance.clone(),
+        paid * sale_config.treasury_share,
+    ));
+    let fees: Uint128 = splits.iter().map(|(_, _, amount)| *amount).sum();
+    splits.push(("dao", dao.clone(), paid - fees));
+
+    Ok(splits)
+}
+
+fn add_sale_splits(
+    mut response: Response,
+    splits: Vec<(&str, Addr, Uint128)>,
+    denom: &str,
+    (sale_key, sale_id): (&str, String),
+) -> Response {
+    for (role, recipient, amount) in splits {
+        if amount.is_zero() {
+            continue;
+        }
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins(amount.u128(), denom),
+            })
+            .add_event(
+                Event::new("art_sale_split")
+                    .add_attribute(sale_key, sale_id.clone())
+                    .add_attribute("role", role)
+                    .add_attribute("recipient", recipient)
+                    .add_attribute("amount", amount.to_string()),
+            );
+    }
+    response
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    receive_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_paused(&config)?;
+
+    let seller = deps.api.addr_validate(&receive_msg.sender)?;
+    let token_id = receive_msg.token_id;
+
+    // Only art NFTs can be traded here
+    if token_collection(deps.storage, &config, &token_id)? != info.sender {
+        return Err(ContractError::UnauthorizedTokenContract {});
+    }
+
+    match from_binary(&receive_msg.msg)? {
+        ReceiveNftMsg::ListForSale { price } => {
+            let sale_order = SaleOrder {
+                token_id,
+                seller,
+                price,
+            };
+            SALE_ORDERS.save(deps.storage, &sale_order.token_id, &sale_order)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "list_for_sale")
+                .add_attribute("token_id", sale_order.token_id)
+                .add_attribute("seller", sale_order.seller)
+                .add_attribute("price", sale_order.price.to_string()))
+        }
+        ReceiveNftMsg::AcceptOffer { buyer } => {
+            let buyer = deps.api.addr_validate(&buyer)?;
+            let offer = load_offer(deps.as_ref(), &token_id, &buyer)?;
+            OFFERS.remove(deps.storage, (token_id.as_str(), &buyer));
+
+            settle_sale(
+                deps.as_ref(),
+                &config,
+                token_id,
+                &seller,
+                &buyer,
+                &offer.price,
+            )
+        }
+    }
+}
+
+pub fn execute_buy(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_paused(&config)?;
+    let sale_order = load_sale_order(deps.as_ref(), &token_id)?;
+
+    let paid = must_pay(&info, &sale_order.price.denom)?;
+    if paid != sale_order.price.amount {
+        return Err(ContractError::WrongPaymentAmount {});
+    }
+
+    SALE_ORDERS.remove(deps.storage, &token_id);
+
+    settle_sale(
+        deps.as_ref(),
+        &config,
+        token_id,
+        &sale_order.seller,
+        &info.sender,
+        &sale_order.price,
+    )
+}
+
+pub fn execute_cancel_sale(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_nft_contracts_are_linked(&config)?;
+    let sale_order = load_sale_order(deps.as_ref(), &token_id)?;
+
+    if sale_order.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    SALE_ORDERS.remove(deps.storage, &token_id);
+
+    let transfer_msg = WasmMsg::Execute {
+        contract_addr: token_collection(deps.storage, &config, &token_id)?.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: sale_order.seller.to_string(),
+            token_id: token_id.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attribute("action", "cancel_sale")
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_make_offer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_paused(&config)?;
+    assert_nft_contracts_are_linked(&config)?;
+    let price = one_coin(&info)?;
+
+    // Make sure the art NFT exists
+    let _: OwnerOfResponse = deps.querier.query_wasm_smart(
+        token_collection(deps.storage, &config, &token_id)?,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: token_id.clone(),
+            include_expired: None,
+        },
+    )?;
+
+    let mut response = Response::new();
+
+    // Replacing an offer refunds the previous one
+    if let Some(previous) = OFFERS.may_load(deps.storage, (token_id.as_str(), &info.sender))? {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![previous.price],
+        });
+    }
+
+    let offer = Offer {
+        token_id,
+        buyer: info.sender,
+        price,
+    };
+    OFFERS.save(
+        deps.storage,
+        (offer.token_id.as_str(), &offer.buyer),
+        &offer,
+    )?;
+
+    Ok(response
+        .add_attribute("action", "make_offer")
+        .add_attribute("token_id", offer.token_id)
+        .add_attribute("buyer", offer.buyer)
+        .add_attribute("price", offer.price.to_string()))
+}
+
+pub fn execute_cancel_offer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let offer = load_offer(deps.as_ref(), &token_id, &info.sender)?;
+    OFFERS.remove(deps.storage, (token_id.as_str(), &info.sender));
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![offer.price],
+        })
+        .add_attribute("action", "cancel_offer")
+        .add_attribute("token_id", token_id))
+}
+
+pub fn execute_accept_offer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    token_id: String,
+    buyer: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_paused(&config)?;
+    let sale_order = load_sale_order(deps.as_ref(), &token_id)?;
+
+    if sale_order.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let buyer = deps.api.addr_validate(&buyer)?;
+    let offer = load_offer(deps.as_ref(), &token_id, &buyer)?;
+
+    SALE_ORDERS.remove(deps.storage, &token_id);
+    OFFERS.remove(deps.storage, (token_id.as_str(), &buyer));
+
+    settle_sale(
+        deps.as_ref(),
+        &config,
+        token_id,
+        &sale_order.seller,
+        &buyer,
+        &offer.price,
+    )
+}
+
+fn load_sale_order(deps: Deps, token_id: &str) -> Result<SaleOrder, ContractError> {
+    SALE_ORDERS
+        .may_load(deps.storage, token_id)?
+        .ok_or_else(|| ContractError::NotForSale {
+            token_id: token_id.to_string(),
+        })
+}
+
+fn load_offer(deps: Deps, token_id: &str, buyer: &Addr) -> Result<Offer, ContractError> {
+    OFFERS
+        .may_load(deps.storage, (token_id, buyer))?
+        .ok_or_else(|| ContractError::OfferNotFound {
+            token_id: token_id.to_string(),
+            buyer: buyer.to_string(),
+        })
+}
+
+// Pays the royalty and the seller and sends the escrowed art NFT to the buyer
+fn settle_sale(
+    deps: Deps,
+    config: &Config,
+    token_id: String,
+    seller: &Addr,
+    buyer: &Addr,
+    price: &Coin,
+) -> Result<Response, ContractError> {
+    assert_nft_contracts_are_linked(config)?;
+    let royalty = query_royalty_info(deps, config, token_id.clone(), price.amount)?;
+    let seller_amount = price.amount - royalty.royalty_amount;
+
+    let mut response = Response::new().add_message(WasmMsg::Execute {
+        contract_addr: token_collection(deps.storage, config, &token_id)?.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: buyer.to_string(),
+            token_id: token_id.clone(),
+        })?,
+        funds: vec![],
+    });
+
+    if !royalty.royalty_amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: royalty.address.clone(),
+            amount: coins(royalty.royalty_amount.u128(), &price.denom),
+        });
+    }
+    if !seller_amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: seller.to_string(),
+            amount: coins(seller_amount.u128(), &price.denom),
+        });
+    }
+
+    Ok(response.add_event(
+        Event::new("art_trade")
+            .add_attribute("token_id", token_id)
+            .add_attribute("seller", seller)
+            .add_attribute("buyer", buyer)
+            .add_attribute("price", price.to_string())
+            .add_attribute("royalty_recipient", royalty.address)
+            .add_attribute("royalty_amount", royalty.royalty_amount.to_string()),
+    ))
+}
+
+// Art NFTs minted before the DAO had a collection are in the shared art NFT contract
+fn token_collection(storage: &dyn Storage, config: &Config, token_id: &str) -> StdResult<Addr> {
+    match TOKEN_COLLECTIONS.may_load(storage, token_id)? {
+        Some(collection) => Ok(collection),
+        None => config
+            .art_nft_address
+            .clone()
+            .ok_or_else(|| StdError::generic_err(ContractError::Cw721NotLinked {}.to_string())),
+    }
+}
+
+fn assert_not_paused(config: &Config) -> Result<(), ContractError> {
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
 }
 
 fn assert_nft_contracts_are_linked(config: &Config) -> Result<(), ContractError> {
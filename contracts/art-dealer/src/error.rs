@@ -1,7 +1,8 @@
 use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
@@ -38,4 +39,67 @@ pub enum ContractError {
 
     #[error("DealerNotApproved")]
     DealerNotApproved {},
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("SalesDisabled")]
+    SalesDisabled {},
+
+    #[error("InvalidSaleShares")]
+    InvalidSaleShares {},
+
+    #[error("ListingNotFound")]
+    ListingNotFound { listing_id: u64 },
+
+    #[error("Paused")]
+    Paused {},
+
+    #[error("InvalidRoyaltyPercentage")]
+    InvalidRoyaltyPercentage {},
+
+    #[error("NotForSale")]
+    NotForSale { token_id: String },
+
+    #[error("OfferNotFound")]
+    OfferNotFound { token_id: String, buyer: String },
+
+    #[error("VoucherSignerNotSet")]
+    VoucherSignerNotSet {},
+
+    #[error("InvalidVoucherSignature")]
+    InvalidVoucherSignature {},
+
+    #[error("VoucherExpired")]
+    VoucherExpired {},
+
+    #[error("VoucherAlreadyRedeemed")]
+    VoucherAlreadyRedeemed { token_id: String },
+
+    #[error("VoucherBuyerMismatch")]
+    VoucherBuyerMismatch {},
+
+    #[error("CollectionsDisabled")]
+    CollectionsDisabled {},
+
+    #[error("CollectionExists")]
+    CollectionExists {},
+
+    #[error("TokenIdTaken")]
+    TokenIdTaken { token_id: String },
+
+    #[error("MetadataTooLarge")]
+    MetadataTooLarge {},
+
+    #[error("TooManyAttributes")]
+    TooManyAttributes {},
+
+    #[error("InvalidUri")]
+    InvalidUri { uri: String },
+
+    #[error("InvalidContentHash")]
+    InvalidContentHash {},
+
+    #[error("BatchTooLarge")]
+    BatchTooLarge { max: usize },
 }
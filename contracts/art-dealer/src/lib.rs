@@ -1,6 +1,9 @@
 pub mod contract;
 mod error;
 pub mod msg;
+#[cfg(any(test, feature = "tests"))]
+pub mod multitest;
 pub mod state;
+pub mod upgrades;
 
 pub use crate::error::ContractError;
@@ -1,15 +1,29 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Uint128};
+use cw721::Cw721ReceiveMsg;
 use cw721_metadata_onchain::Metadata;
+
+use crate::state::{Approval, ApprovalEvent, Listing, Offer, Provenance, SaleOrder};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub owner: Addr,
+    #[serde(alias = "owner")]
+    pub governance: Addr,
+    #[serde(default)]
+    pub admin: Option<Addr>,
+    #[serde(default)]
+    pub pauser: Option<Addr>,
     pub identityservice_contract: Addr,
     pub art_nft_name: String,
     pub art_nft_symbol: String,
     pub art_nft_code_id: u64,
+    #[serde(default)]
+    pub reclaim_quota_on_burn: bool,
+    #[serde(default)]
+    pub royalty_percentage: u64,
+    #[serde(default)]
+    pub collection_per_dao: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -26,28 +40,313 @@ pub enum ExecuteMsg {
         // see: https://docs.opensea.io/docs/metadata-standards
         metadata: Option<Metadata>,
     },
+    /// Mints all items at once, the approval has to cover the whole batch.
+    /// At most 50 items can be minted per batch
+    BatchMintArt {
+        items: Vec<MintItem>,
+    },
+    /// Sets the public key the sender DAO signs its mint vouchers with, `None` stops redemptions
+    SetVoucherSigner {
+        pubkey: Option<Binary>,
+    },
+    /// Mints the piece of a voucher signed by the DAO to the sender, the voucher price has to be sent along
+    RedeemVoucher {
+        voucher: MintVoucher,
+        /// secp256k1 signature (64 bytes, r and s) of the sha256 hash of the art-dealer
+        /// address followed by the JSON encoded voucher, see `MintVoucher`
+        signature: Binary,
+    },
+    /// Creates a cw721 collection for the sender DAO, its art NFTs are minted there from then on.
+    /// Defaults to the name of the DAO and the symbol of the art NFT contract
+    CreateCollection {
+        name: Option<String>,
+        symbol: Option<String>,
+    },
+    /// Can be called by governance or the admin
+    UpdateCollectionConfig {
+        art_nft_code_id: Option<u64>,
+        collection_per_dao: Option<bool>,
+    },
     ApproveDealer {
         dao: Addr,
         approved: u64,
         // Time in seconds
         duration: u64,
+        // Governance proposal the approval was decided in
+        #[serde(default)]
+        proposal_id: Option<u64>,
     },
+    /// Can be called by governance or by the DAO itself
     RevokeDealer {
         dao: Addr,
     },
+    /// Governance can update every role, the admin only the admin and the pauser
+    UpdateRoles {
+        governance: Option<String>,
+        admin: Option<String>,
+        pauser: Option<String>,
+    },
+    /// Stops minting and trading until unpaused, can be called by the pauser or governance
+    Pause {},
+    Unpause {},
+    /// Burns an art NFT of the sender, art-dealer has to be approved for the token first
+    BurnArt {
+        token_id: String,
+    },
+    /// Offers a piece for sale, it is minted to the buyer on purchase
+    ListArt {
+        token_id: String,
+        token_uri: Option<String>,
+        metadata: Option<Metadata>,
+        /// Price in the denom of the sale config
+        price: Uint128,
+    },
+    CancelListing {
+        listing_id: u64,
+    },
+    /// Mints the listed piece to the sender, the price has to be sent along
+    Purchase {
+        listing_id: u64,
+    },
+    /// Can be called by governance or the admin
+    UpdateSaleConfig {
+        denom: Option<String>,
+        treasury_share: Decimal,
+        fee_recipients: Vec<FeeRecipientMsg>,
+    },
+    /// Art NFTs sent with `SendNft`, see `ReceiveNftMsg`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Buys an escrowed art NFT, the price has to be sent along
+    Buy {
+        token_id: String,
+    },
+    /// Returns an escrowed art NFT to its seller
+    CancelSale {
+        token_id: String,
+    },
+    /// Offers the sent funds for an art NFT, replaces a previous offer of the sender
+    MakeOffer {
+        token_id: String,
+    },
+    CancelOffer {
+        token_id: String,
+    },
+    /// Accepts an offer for an escrowed art NFT of the sender
+    AcceptOffer {
+        token_id: String,
+        buyer: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintItem {
+    pub token_id: String,
+    pub owner: String,
+    pub token_uri: Option<String>,
+    pub metadata: Option<Metadata>,
+}
+
+/// Signed off-chain by an approved DAO, the buyer or anyone holding it when no buyer is set
+/// can mint the piece to themselves.
+///
+/// The signed message is the bech32 address of the art-dealer contract followed by the voucher
+/// encoded with serde-json-wasm: the fields in the order below without whitespace, `None` as
+/// `null` except for `buyer`, which is left out when unset, and `Uint128` amounts as strings, e.g.
+/// `{"dao":"jmes1...","token_id":"art1","token_uri":null,"metadata":null,
+/// "price":{"denom":"ujmes","amount":"1000"},"expires":1200000,"buyer":"jmes1..."}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintVoucher {
+    pub dao: String,
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    pub metadata: Option<Metadata>,
+    /// Paid to the DAO, split like primary sales
+    pub price: Option<Coin>,
+    /// Last block height the voucher can be redeemed at
+    pub expires: u64,
+    /// Only this address can redeem the voucher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buyer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveNftMsg {
+    /// Keeps the art NFT in escrow until it is bought for the price
+    ListForSale { price: Coin },
+    /// Sells the art NFT to the buyer of an existing offer
+    AcceptOffer { buyer: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipientMsg {
+    pub address: String,
+    pub share: Decimal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetConfig {},
+    Approval {
+        dao: String,
+    },
+    Approvals {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        // Only return approvals that are neither expired nor used up
+        active_only: Option<bool>,
+    },
+    ApprovalHistory {
+        dao: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Returns the DAO that minted the art NFT
+    ArtMinter {
+        token_id: String,
+    },
+    SaleConfig {},
+    Listing {
+        listing_id: u64,
+    },
+    Listings {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    SaleOrder {
+        token_id: String,
+    },
+    SaleOrders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Offers {
+        token_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    VoucherSigner {
+        dao: String,
+    },
+    // Whether the voucher of the DAO for the token id has been redeemed
+    VoucherRedeemed {
+        dao: String,
+        token_id: String,
+    },
+    Collections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns the DAO, height, proposal and content hash of the mint of the art NFT
+    ArtProvenance {
+        token_id: String,
+    },
+    // Forwards the cw2981 query of the same name to the collection of the art NFT
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
-    pub owner: Addr,
+    pub governance: Addr,
+    pub admin: Option<Addr>,
+    pub pauser: Option<Addr>,
+    pub paused: bool,
     pub identityservice_contract: Addr,
     pub art_nft_address: Option<Addr>,
     pub art_nft_name: String,
     pub art_nft_symbol: String,
+    pub reclaim_quota_on_burn: bool,
+    pub royalty_percentage: u64,
+    pub art_nft_code_id: u64,
+    pub collection_per_dao: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovalResponse {
+    pub approval: Option<Approval>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DaoApproval {
+    pub dao: Addr,
+    pub approval: Approval,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovalsResponse {
+    pub approvals: Vec<DaoApproval>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovalHistoryResponse {
+    pub events: Vec<ApprovalEvent>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ArtMinterResponse {
+    pub dao: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingResponse {
+    pub listing: Option<Listing>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingsResponse {
+    pub listings: Vec<Listing>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SaleOrderResponse {
+    pub sale_order: Option<SaleOrder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SaleOrdersResponse {
+    pub sale_orders: Vec<SaleOrder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OffersResponse {
+    pub offers: Vec<Offer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherSignerResponse {
+    pub pubkey: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherRedeemedResponse {
+    pub redeemed_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DaoCollection {
+    pub dao: Addr,
+    pub collection: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionsResponse {
+    pub collections: Vec<DaoCollection>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ArtProvenanceResponse {
+    pub provenance: Option<Provenance>,
 }
@@ -0,0 +1,535 @@
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, StdResult, Uint128};
+use cw721_metadata_onchain::Metadata;
+use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
+
+use crate::contract::{execute, instantiate, migrate, query, reply};
+use crate::msg::{
+    ApprovalHistoryResponse, ApprovalResponse, ApprovalsResponse, ArtMinterResponse,
+    ArtProvenanceResponse, CollectionsResponse, ConfigResponse, ExecuteMsg, FeeRecipientMsg,
+    InstantiateMsg, ListingResponse, MigrateMsg, MintVoucher, OffersResponse, QueryMsg,
+    RoyaltiesInfoResponse, SaleOrderResponse,
+};
+use crate::ContractError;
+
+#[derive(Debug, Clone)]
+pub struct ArtDealerContract(Addr);
+
+impl ArtDealerContract {
+    pub fn addr(&self) -> &Addr {
+        &self.0
+    }
+
+    pub fn store_code(app: &mut App) -> u64 {
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_reply(reply);
+        app.store_code(Box::new(contract))
+    }
+
+    #[track_caller]
+    pub fn instantiate(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        msg: &InstantiateMsg,
+    ) -> StdResult<Self> {
+        app.instantiate_contract(
+            code_id,
+            sender.clone(),
+            msg,
+            &[],
+            label,
+            Some(sender.to_string()),
+        )
+        .map(ArtDealerContract)
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn execute(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        msg: &ExecuteMsg,
+        funds: &[Coin],
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(sender.clone(), self.0.clone(), msg, funds)
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn migrate(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        new_code_id: u64,
+    ) -> Result<AppResponse, ContractError> {
+        app.migrate_contract(sender.clone(), self.0.clone(), &MigrateMsg {}, new_code_id)
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn update_roles(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        governance: Option<&str>,
+        admin: Option<&str>,
+        pauser: Option<&str>,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::UpdateRoles {
+                governance: governance.map(String::from),
+                admin: admin.map(String::from),
+                pauser: pauser.map(String::from),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn pause(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        self.execute(app, sender, &ExecuteMsg::Pause {}, &[])
+    }
+
+    #[track_caller]
+    pub fn unpause(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        self.execute(app, sender, &ExecuteMsg::Unpause {}, &[])
+    }
+
+    #[track_caller]
+    pub fn approve_dealer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        dao: &Addr,
+        approved: u64,
+        duration: u64,
+        proposal_id: Option<u64>,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::ApproveDealer {
+                dao: dao.clone(),
+                approved,
+                duration,
+                proposal_id,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn revoke_dealer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        dao: &Addr,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::RevokeDealer { dao: dao.clone() },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn mint_art(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+        owner: &Addr,
+        metadata: Option<Metadata>,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::MintArt {
+                token_id: token_id.to_string(),
+                owner: owner.to_string(),
+                token_uri: None,
+                metadata,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn burn_art(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::BurnArt {
+                token_id: token_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn create_collection(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        name: Option<&str>,
+        symbol: Option<&str>,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::CreateCollection {
+                name: name.map(String::from),
+                symbol: symbol.map(String::from),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn update_collection_config(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        art_nft_code_id: Option<u64>,
+        collection_per_dao: Option<bool>,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::UpdateCollectionConfig {
+                art_nft_code_id,
+                collection_per_dao,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn set_voucher_signer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        pubkey: Option<Binary>,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(app, sender, &ExecuteMsg::SetVoucherSigner { pubkey }, &[])
+    }
+
+    #[track_caller]
+    pub fn redeem_voucher(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        voucher: &MintVoucher,
+        signature: &Binary,
+        funds: &[Coin],
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::RedeemVoucher {
+                voucher: voucher.clone(),
+                signature: signature.clone(),
+            },
+            funds,
+        )
+    }
+
+    #[track_caller]
+    pub fn update_sale_config(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        denom: Option<String>,
+        treasury_share: Decimal,
+        fee_recipients: Vec<FeeRecipientMsg>,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::UpdateSaleConfig {
+                denom,
+                treasury_share,
+                fee_recipients,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn list_art(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+        price: u128,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::ListArt {
+                token_id: token_id.to_string(),
+                token_uri: None,
+                metadata: None,
+                price: Uint128::new(price),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn cancel_listing(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        listing_id: u64,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(app, sender, &ExecuteMsg::CancelListing { listing_id }, &[])
+    }
+
+    #[track_caller]
+    pub fn purchase(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        listing_id: u64,
+        funds: &[Coin],
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(app, sender, &ExecuteMsg::Purchase { listing_id }, funds)
+    }
+
+    #[track_caller]
+    pub fn buy(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+        funds: &[Coin],
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::Buy {
+                token_id: token_id.to_string(),
+            },
+            funds,
+        )
+    }
+
+    #[track_caller]
+    pub fn cancel_sale(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::CancelSale {
+                token_id: token_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn make_offer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+        funds: &[Coin],
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::MakeOffer {
+                token_id: token_id.to_string(),
+            },
+            funds,
+        )
+    }
+
+    #[track_caller]
+    pub fn cancel_offer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::CancelOffer {
+                token_id: token_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn accept_offer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        token_id: &str,
+        buyer: &Addr,
+    ) -> Result<AppResponse, ContractError> {
+        self.execute(
+            app,
+            sender,
+            &ExecuteMsg::AcceptOffer {
+                token_id: token_id.to_string(),
+                buyer: buyer.to_string(),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn query_config(&self, app: &mut App) -> StdResult<ConfigResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::GetConfig {})
+    }
+
+    #[track_caller]
+    pub fn query_approval(&self, app: &mut App, dao: &Addr) -> StdResult<ApprovalResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Approval {
+                dao: dao.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_approvals(
+        &self,
+        app: &mut App,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        active_only: Option<bool>,
+    ) -> StdResult<ApprovalsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Approvals {
+                start_after,
+                limit,
+                active_only,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_approval_history(
+        &self,
+        app: &mut App,
+        dao: &Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ApprovalHistoryResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ApprovalHistory {
+                dao: dao.to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_listing(&self, app: &mut App, listing_id: u64) -> StdResult<ListingResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Listing { listing_id })
+    }
+
+    #[track_caller]
+    pub fn query_sale_order(&self, app: &mut App, token_id: &str) -> StdResult<SaleOrderResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::SaleOrder {
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_offers(&self, app: &mut App, token_id: &str) -> StdResult<OffersResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Offers {
+                token_id: token_id.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_royalty_info(
+        &self,
+        app: &mut App,
+        token_id: &str,
+        sale_price: u128,
+    ) -> StdResult<RoyaltiesInfoResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::RoyaltyInfo {
+                token_id: token_id.to_string(),
+                sale_price: Uint128::new(sale_price),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_art_provenance(
+        &self,
+        app: &mut App,
+        token_id: &str,
+    ) -> StdResult<ArtProvenanceResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ArtProvenance {
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_collections(
+        &self,
+        app: &mut App,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<CollectionsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Collections {
+                start_after: start_after.map(|dao| dao.to_string()),
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_art_minter(&self, app: &mut App, token_id: &str) -> StdResult<ArtMinterResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ArtMinter {
+                token_id: token_id.to_string(),
+            },
+        )
+    }
+}
@@ -0,0 +1,1822 @@
+use cosmwasm_std::{
+    coins, from_binary, to_binary, to_vec, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env,
+    MessageInfo, Reply, Response, StdResult, Uint128,
+};
+use cw4::Member;
+use cw721::{ContractInfoResponse, Cw721ReceiveMsg, OwnerOfResponse};
+use cw721_metadata_onchain::{
+    ExecuteMsg as Cw721ExecuteMsg, Metadata, QueryMsg as Cw721QueryMsg, Trait,
+};
+use cw_multi_test::{next_block, App, AppBuilder, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use cw_utils::{Duration, PaymentError};
+use dao_members::multitest::contract::DaoMembersContract;
+use dao_multisig::multitest::contract::DaoMultisigContract;
+use identityservice::multitest::contract::IdentityserviceContract;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{execute, instantiate, query, reply, CONTRACT_NAME};
+use crate::msg::{
+    ExecuteMsg, FeeRecipientMsg, InstantiateMsg, MigrateMsg, MintItem, MintVoucher, ReceiveNftMsg,
+};
+use crate::state::{ApprovalAction, Provenance, CONFIG};
+use crate::upgrades;
+use crate::ContractError;
+
+use super::contract::ArtDealerContract;
+
+const OWNER: &str = "owner";
+const GOVERNANCE: &str = "governance";
+const ADMIN: &str = "admin";
+const PAUSER: &str = "pauser";
+const USER1: &str = "user1";
+const USER2: &str = "user2";
+const BUYER: &str = "buyer";
+const FEE_RECIPIENT: &str = "fee_recipient";
+const ARTIST: &str = "artist";
+
+// Compressed secp256k1 public key of the private key
+// fffa2d7750376ab19af012175991aa863599b065bb7f173edad007227e2ca1a7
+const VOUCHER_PUBKEY: &str = "A3HAzqYlY5MbZEpx+3GhIQ+1ruf0E16zC6Tz9ypyJ4xw";
+// Signatures of "contract1" (art-dealer) followed by the vouchers, both by the DAO contract4
+const PRICED_VOUCHER: &str = r#"{"dao":"contract4","token_id":"art1","token_uri":"ipfs://art1","metadata":null,"price":{"denom":"ujmes","amount":"1000"},"expires":100000,"buyer":"buyer"}"#;
+const PRICED_VOUCHER_SIGNATURE: &str =
+    "xf4BM97C56+10PC4P5uk/G/e5hc8SCj7yWN3DIziL4gAY0z+DZfi+UMqzUCYDiUAemYiEbFQYkznEltDKKVYkQ==";
+const FREE_VOUCHER: &str = r#"{"dao":"contract4","token_id":"art2","token_uri":null,"metadata":null,"price":null,"expires":100000}"#;
+const FREE_VOUCHER_SIGNATURE: &str =
+    "NKbxEs5U2ypUx/xA2uv9ZBVJr0OGSZBCLr7wgkXhSoRCT2iTY/tVo8akU1I6YLLfsHh/EhLkb3vwlr2jn6rpRg==";
+
+const DENOM: &str = "ujmes";
+const BUYER_FUNDS: u128 = 1_000_000;
+
+struct Contracts {
+    art_dealer: ArtDealerContract,
+    identityservice: IdentityserviceContract,
+    art_nft: Addr,
+}
+
+// Helper functions to keep the tests more succinct
+
+fn mock_app() -> App {
+    AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(BUYER), coins(BUYER_FUNDS, DENOM))
+            .unwrap();
+    })
+}
+
+fn balance(app: &App, addr: &str) -> u128 {
+    app.wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+}
+
+fn store_art_nft_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(
+        cw721_metadata_onchain::entry::execute,
+        cw721_metadata_onchain::entry::instantiate,
+        cw721_metadata_onchain::entry::query,
+    );
+    app.store_code(Box::new(contract))
+}
+
+fn instantiate_contracts(app: &mut App) -> Contracts {
+    instantiate_contracts_with(app, |_| {})
+}
+
+// Instantiates identityservice and art-dealer, `update_msg` can change the art-dealer config
+fn instantiate_contracts_with(
+    app: &mut App,
+    update_msg: impl FnOnce(&mut InstantiateMsg),
+) -> Contracts {
+    let art_dealer_code_id = ArtDealerContract::store_code(app);
+    instantiate_contracts_with_code(app, art_dealer_code_id, update_msg)
+}
+
+fn instantiate_contracts_with_code(
+    app: &mut App,
+    art_dealer_code_id: u64,
+    update_msg: impl FnOnce(&mut InstantiateMsg),
+) -> Contracts {
+    let owner = Addr::unchecked(OWNER);
+
+    let dao_members_code_id = DaoMembersContract::store_code(app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(app);
+    let identityservice_code_id = IdentityserviceContract::store_code(app);
+    let identityservice = IdentityserviceContract::instantiate(
+        app,
+        identityservice_code_id,
+        &owner,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked(GOVERNANCE),
+    )
+    .unwrap();
+
+    let art_nft_code_id = store_art_nft_code(app);
+    let mut msg = InstantiateMsg {
+        governance: Addr::unchecked(GOVERNANCE),
+        admin: Some(Addr::unchecked(ADMIN)),
+        pauser: Some(Addr::unchecked(PAUSER)),
+        identityservice_contract: identityservice.addr().clone(),
+        art_nft_name: "Art NFT".into(),
+        art_nft_symbol: "ART".into(),
+        art_nft_code_id,
+        reclaim_quota_on_burn: false,
+        royalty_percentage: 0,
+        collection_per_dao: false,
+    };
+    update_msg(&mut msg);
+    let art_dealer =
+        ArtDealerContract::instantiate(app, art_dealer_code_id, &owner, "art-dealer", &msg)
+            .unwrap();
+
+    // The shared art NFT contract is linked on instantiate reply
+    let art_nft = art_dealer
+        .query_config(app)
+        .unwrap()
+        .art_nft_address
+        .unwrap();
+
+    Contracts {
+        art_dealer,
+        identityservice,
+        art_nft,
+    }
+}
+
+// Config layout of the last release with a single owner
+#[derive(Serialize, Deserialize)]
+struct LegacyConfig {
+    owner: Addr,
+    identityservice_contract: Addr,
+    art_nft_address: Option<Addr>,
+    art_nft_name: String,
+    art_nft_symbol: String,
+}
+
+const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+
+// Links the art NFT contract like the current code, but leaves the state of the last release
+// without migrations behind
+fn legacy_reply(mut deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let response = reply(deps.branch(), env, msg)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    LEGACY_CONFIG.save(
+        deps.storage,
+        &LegacyConfig {
+            owner: config.governance,
+            identityservice_contract: config.identityservice_contract,
+            art_nft_address: config.art_nft_address,
+            art_nft_name: config.art_nft_name,
+            art_nft_symbol: config.art_nft_symbol,
+        },
+    )?;
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, upgrades::v0_15::FROM_VERSION)?;
+
+    Ok(response)
+}
+
+fn store_legacy_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(legacy_reply);
+    app.store_code(Box::new(contract))
+}
+
+// Art NFT metadata of the cw721 code the shared art NFT of migrated deployments still runs,
+// it predates royalties and content hashes and rejects unknown fields
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct LegacyMetadata {
+    image: Option<String>,
+    image_data: Option<String>,
+    external_url: Option<String>,
+    description: Option<String>,
+    name: Option<String>,
+    attributes: Option<Vec<Trait>>,
+    background_color: Option<String>,
+    animation_url: Option<String>,
+    youtube_url: Option<String>,
+}
+
+// No cw2981 queries either
+type LegacyArtNftContract<'a> =
+    cw721_base::Cw721Contract<'a, Option<LegacyMetadata>, Empty, Empty, Empty>;
+
+fn legacy_art_nft_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::InstantiateMsg,
+) -> StdResult<Response> {
+    LegacyArtNftContract::default().instantiate(deps, env, info, msg)
+}
+
+fn legacy_art_nft_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Option<LegacyMetadata>, Empty>,
+) -> Result<Response, cw721_base::ContractError> {
+    LegacyArtNftContract::default().execute(deps, env, info, msg)
+}
+
+fn legacy_art_nft_query(
+    deps: Deps,
+    env: Env,
+    msg: cw721_base::QueryMsg<Empty>,
+) -> StdResult<Binary> {
+    LegacyArtNftContract::default().query(deps, env, msg)
+}
+
+fn store_legacy_art_nft_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(
+        legacy_art_nft_execute,
+        legacy_art_nft_instantiate,
+        legacy_art_nft_query,
+    );
+    app.store_code(Box::new(contract))
+}
+
+fn create_dao(app: &mut App, contracts: &Contracts, name: &str) -> Addr {
+    let response = contracts
+        .identityservice
+        .register_dao(
+            app,
+            &Addr::unchecked(USER1),
+            vec![
+                Member {
+                    addr: USER1.into(),
+                    weight: 26,
+                },
+                Member {
+                    addr: USER2.into(),
+                    weight: 26,
+                },
+            ],
+            name.to_string(),
+            51u64,
+            Duration::Time(2000000),
+        )
+        .unwrap();
+
+    app.update_block(next_block);
+    from_binary::<dao_multisig::msg::InstantiateResponse>(&response.data.unwrap())
+        .unwrap()
+        .dao_multisig_addr
+}
+
+fn owner_of(app: &App, collection: &Addr, token_id: &str) -> String {
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap()
+        .owner
+}
+
+fn contract_info(app: &App, collection: &Addr) -> ContractInfoResponse {
+    app.wrap()
+        .query_wasm_smart(collection, &Cw721QueryMsg::ContractInfo {})
+        .unwrap()
+}
+
+// Lets art-dealer burn or transfer the art NFT of the owner
+fn approve_art_dealer(
+    app: &mut App,
+    contracts: &Contracts,
+    collection: &Addr,
+    owner: &Addr,
+    token_id: &str,
+) {
+    app.execute_contract(
+        owner.clone(),
+        collection.clone(),
+        &Cw721ExecuteMsg::Approve {
+            spender: contracts.art_dealer.addr().to_string(),
+            token_id: token_id.to_string(),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+// Sends the art NFT of the owner to art-dealer with the receive msg
+fn send_to_art_dealer(
+    app: &mut App,
+    contracts: &Contracts,
+    owner: &Addr,
+    token_id: &str,
+    msg: &ReceiveNftMsg,
+) {
+    app.execute_contract(
+        owner.clone(),
+        contracts.art_nft.clone(),
+        &Cw721ExecuteMsg::SendNft {
+            contract: contracts.art_dealer.addr().to_string(),
+            token_id: token_id.to_string(),
+            msg: to_binary(msg).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+// The actual tests
+#[test]
+fn approval_quota_and_history() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+
+    let contracts = instantiate_contracts(&mut app);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    let other_dao = create_dao(&mut app, &contracts, "otherdao");
+
+    // Approvals are only decided by governance
+    let err = contracts
+        .art_dealer
+        .approve_dealer(&mut app, &dao, &dao, 2, 100, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Only approved DAOs can mint
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &user1, "art1", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art1", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::DealerNotApproved {});
+
+    let approved_at = app.block_info().height;
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 2, 100, Some(7))
+        .unwrap();
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &other_dao, 1, 100, None)
+        .unwrap();
+
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art1", &user1, None)
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art1"), USER1);
+    assert_eq!(
+        contracts
+            .art_dealer
+            .query_art_minter(&mut app, "art2")
+            .unwrap()
+            .dao,
+        Some(dao.clone())
+    );
+
+    // The approved amount can't be exceeded
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art3", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovedExceeded {});
+
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &dao)
+        .unwrap()
+        .approval
+        .unwrap();
+    assert_eq!(approval.minted, 2);
+    assert_eq!(approval.expires, approved_at + 100);
+    assert_eq!(approval.proposal_id, Some(7));
+
+    // Used up approvals are not active anymore
+    let approvals = contracts
+        .art_dealer
+        .query_approvals(&mut app, None, None, None)
+        .unwrap()
+        .approvals;
+    assert_eq!(approvals.len(), 2);
+    let approvals = contracts
+        .art_dealer
+        .query_approvals(&mut app, None, None, Some(true))
+        .unwrap()
+        .approvals;
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals[0].dao, other_dao);
+
+    // Neither are expired ones
+    app.update_block(|block| block.height += 101);
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &other_dao, "art3", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovalExpired {});
+    let approvals = contracts
+        .art_dealer
+        .query_approvals(&mut app, None, None, Some(true))
+        .unwrap()
+        .approvals;
+    assert!(approvals.is_empty());
+
+    // Besides governance, only the DAO itself can revoke its approval
+    let err = contracts
+        .art_dealer
+        .revoke_dealer(&mut app, &user1, &other_dao)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .revoke_dealer(&mut app, &other_dao, &other_dao)
+        .unwrap();
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &other_dao)
+        .unwrap()
+        .approval;
+    assert!(approval.is_none());
+
+    // There is nothing left to revoke
+    let err = contracts
+        .art_dealer
+        .revoke_dealer(&mut app, &governance, &other_dao)
+        .unwrap_err();
+    assert_eq!(err, ContractError::DealerNotApproved {});
+
+    // Every approve, mint and revoke is recorded
+    let events = contracts
+        .art_dealer
+        .query_approval_history(&mut app, &dao, None, None)
+        .unwrap()
+        .events;
+    let actions: Vec<ApprovalAction> = events.iter().map(|event| event.action.clone()).collect();
+    assert_eq!(
+        actions,
+        vec![
+            ApprovalAction::Approve {
+                approved: 2,
+                expires: approved_at + 100,
+                proposal_id: Some(7),
+            },
+            ApprovalAction::Mint {
+                token_id: "art1".into(),
+            },
+            ApprovalAction::Mint {
+                token_id: "art2".into(),
+            },
+        ]
+    );
+    assert_eq!(events[0].height, approved_at);
+
+    // The history is paginated by event id
+    let next_events = contracts
+        .art_dealer
+        .query_approval_history(&mut app, &dao, Some(events[1].id), Some(1))
+        .unwrap()
+        .events;
+    assert_eq!(next_events, vec![events[2].clone()]);
+
+    let events = contracts
+        .art_dealer
+        .query_approval_history(&mut app, &other_dao, None, None)
+        .unwrap()
+        .events;
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].action, ApprovalAction::Revoke {});
+}
+
+#[test]
+fn burn_reclaims_quota_of_the_current_approval() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+
+    let contracts = instantiate_contracts_with(&mut app, |msg| msg.reclaim_quota_on_burn = true);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 1, 100, None)
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art1", &user1, None)
+        .unwrap();
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovedExceeded {});
+
+    // Only the owner can burn its art NFT
+    approve_art_dealer(&mut app, &contracts, &contracts.art_nft, &user1, "art1");
+    let err = contracts
+        .art_dealer
+        .burn_art(&mut app, &user2, "art1")
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .burn_art(&mut app, &user1, "art1")
+        .unwrap();
+
+    // The burned NFT frees up its quota
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &dao)
+        .unwrap()
+        .approval
+        .unwrap();
+    assert_eq!((approval.minted, approval.burned), (1, 1));
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap();
+
+    let events = contracts
+        .art_dealer
+        .query_approval_history(&mut app, &dao, None, None)
+        .unwrap()
+        .events;
+    assert_eq!(
+        events[2].action,
+        ApprovalAction::Burn {
+            token_id: "art1".into(),
+        }
+    );
+
+    // NFTs minted under the previous approval don't free up the quota of a new one
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 1, 100, None)
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art3", &user1, None)
+        .unwrap();
+    approve_art_dealer(&mut app, &contracts, &contracts.art_nft, &user1, "art2");
+    contracts
+        .art_dealer
+        .burn_art(&mut app, &user1, "art2")
+        .unwrap();
+
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &dao)
+        .unwrap()
+        .approval
+        .unwrap();
+    assert_eq!((approval.minted, approval.burned), (1, 0));
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art4", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovedExceeded {});
+}
+
+#[test]
+fn primary_sale_splits() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let admin = Addr::unchecked(ADMIN);
+    let user1 = Addr::unchecked(USER1);
+    let buyer = Addr::unchecked(BUYER);
+
+    let contracts = instantiate_contracts(&mut app);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    let other_dao = create_dao(&mut app, &contracts, "otherdao");
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 2, 100, None)
+        .unwrap();
+
+    // Nothing can be listed before the sale denom is set
+    let err = contracts
+        .art_dealer
+        .list_art(&mut app, &dao, "art1", 1001)
+        .unwrap_err();
+    assert_eq!(err, ContractError::SalesDisabled {});
+
+    let fee_recipients = vec![FeeRecipientMsg {
+        address: FEE_RECIPIENT.into(),
+        share: Decimal::percent(3),
+    }];
+    let err = contracts
+        .art_dealer
+        .update_sale_config(
+            &mut app,
+            &user1,
+            Some(DENOM.into()),
+            Decimal::percent(10),
+            fee_recipients.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = contracts
+        .art_dealer
+        .update_sale_config(
+            &mut app,
+            &admin,
+            Some(DENOM.into()),
+            Decimal::percent(98),
+            fee_recipients.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSaleShares {});
+    contracts
+        .art_dealer
+        .update_sale_config(
+            &mut app,
+            &admin,
+            Some(DENOM.into()),
+            Decimal::percent(10),
+            fee_recipients,
+        )
+        .unwrap();
+
+    // Listing requires an approval
+    let err = contracts
+        .art_dealer
+        .list_art(&mut app, &other_dao, "art1", 1001)
+        .unwrap_err();
+    assert_eq!(err, ContractError::DealerNotApproved {});
+
+    contracts
+        .art_dealer
+        .list_art(&mut app, &dao, "art1", 1001)
+        .unwrap();
+    let listing = contracts
+        .art_dealer
+        .query_listing(&mut app, 1)
+        .unwrap()
+        .listing
+        .unwrap();
+    assert_eq!(listing.price, coins(1001, DENOM)[0]);
+
+    let err = contracts
+        .art_dealer
+        .purchase(&mut app, &buyer, 1, &coins(1000, DENOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::WrongPaymentAmount {});
+
+    contracts
+        .art_dealer
+        .purchase(&mut app, &buyer, 1, &coins(1001, DENOM))
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art1"), BUYER);
+    assert!(contracts
+        .art_dealer
+        .query_listing(&mut app, 1)
+        .unwrap()
+        .listing
+        .is_none());
+
+    // Fee recipients and the treasury are rounded down, the DAO gets the remainder
+    assert_eq!(balance(&app, FEE_RECIPIENT), 30);
+    assert_eq!(balance(&app, GOVERNANCE), 100);
+    assert_eq!(balance(&app, dao.as_str()), 871);
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS - 1001);
+
+    // The purchase counts against the approval
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &dao)
+        .unwrap()
+        .approval
+        .unwrap();
+    assert_eq!(approval.minted, 1);
+
+    // Only the DAO can cancel its listing
+    contracts
+        .art_dealer
+        .list_art(&mut app, &dao, "art2", 500)
+        .unwrap();
+    let err = contracts
+        .art_dealer
+        .cancel_listing(&mut app, &user1, 2)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .cancel_listing(&mut app, &dao, 2)
+        .unwrap();
+    let err = contracts
+        .art_dealer
+        .purchase(&mut app, &buyer, 2, &coins(500, DENOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::ListingNotFound { listing_id: 2 });
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS - 1001);
+}
+
+#[test]
+fn escrowed_sales_and_offers() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+    let buyer = Addr::unchecked(BUYER);
+
+    let contracts = instantiate_contracts_with(&mut app, |msg| msg.royalty_percentage = 5);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 3, 100, None)
+        .unwrap();
+
+    // Art NFTs pay the default royalty to their DAO, unless the metadata says otherwise
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art1", &user1, None)
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(
+            &mut app,
+            &dao,
+            "art2",
+            &user1,
+            Some(Metadata {
+                royalty_percentage: Some(10),
+                royalty_payment_address: Some(ARTIST.into()),
+                ..Metadata::default()
+            }),
+        )
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art3", &user1, None)
+        .unwrap();
+
+    let royalty = contracts
+        .art_dealer
+        .query_royalty_info(&mut app, "art1", 1000)
+        .unwrap();
+    assert_eq!(royalty.address, dao.to_string());
+    assert_eq!(royalty.royalty_amount, Uint128::new(50));
+    let royalty = contracts
+        .art_dealer
+        .query_royalty_info(&mut app, "art2", 1000)
+        .unwrap();
+    assert_eq!(royalty.address, ARTIST);
+    assert_eq!(royalty.royalty_amount, Uint128::new(100));
+
+    // Only art NFTs can be traded
+    let err = contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &user1,
+            &ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: user1.to_string(),
+                token_id: "art1".into(),
+                msg: to_binary(&ReceiveNftMsg::ListForSale {
+                    price: coins(1000, DENOM)[0].clone(),
+                })
+                .unwrap(),
+            }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnauthorizedTokenContract {});
+
+    // Cancelled offers are refunded
+    contracts
+        .art_dealer
+        .make_offer(&mut app, &buyer, "art1", &coins(300, DENOM))
+        .unwrap();
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS - 300);
+    contracts
+        .art_dealer
+        .cancel_offer(&mut app, &buyer, "art1")
+        .unwrap();
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS);
+    let err = contracts
+        .art_dealer
+        .cancel_offer(&mut app, &buyer, "art1")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OfferNotFound {
+            token_id: "art1".into(),
+            buyer: BUYER.into(),
+        }
+    );
+
+    // The owner can accept an offer by sending the art NFT
+    contracts
+        .art_dealer
+        .make_offer(&mut app, &buyer, "art1", &coins(400, DENOM))
+        .unwrap();
+    send_to_art_dealer(
+        &mut app,
+        &contracts,
+        &user1,
+        "art1",
+        &ReceiveNftMsg::AcceptOffer {
+            buyer: BUYER.into(),
+        },
+    );
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art1"), BUYER);
+    assert_eq!(balance(&app, dao.as_str()), 20);
+    assert_eq!(balance(&app, USER1), 380);
+
+    // Art NFTs listed for sale are escrowed until the seller cancels the sale
+    let list_for_sale = ReceiveNftMsg::ListForSale {
+        price: Coin::new(2000, DENOM),
+    };
+    send_to_art_dealer(&mut app, &contracts, &user1, "art2", &list_for_sale);
+    assert_eq!(
+        owner_of(&app, &contracts.art_nft, "art2"),
+        contracts.art_dealer.addr().to_string()
+    );
+    let err = contracts
+        .art_dealer
+        .cancel_sale(&mut app, &buyer, "art2")
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .cancel_sale(&mut app, &user1, "art2")
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art2"), USER1);
+    assert!(contracts
+        .art_dealer
+        .query_sale_order(&mut app, "art2")
+        .unwrap()
+        .sale_order
+        .is_none());
+
+    // A new offer replaces and refunds the previous one of the buyer
+    send_to_art_dealer(&mut app, &contracts, &user1, "art2", &list_for_sale);
+    contracts
+        .art_dealer
+        .make_offer(&mut app, &buyer, "art2", &coins(800, DENOM))
+        .unwrap();
+    contracts
+        .art_dealer
+        .make_offer(&mut app, &buyer, "art2", &coins(900, DENOM))
+        .unwrap();
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS - 400 - 900);
+    let offers = contracts
+        .art_dealer
+        .query_offers(&mut app, "art2")
+        .unwrap()
+        .offers;
+    assert_eq!(offers.len(), 1);
+    assert_eq!(offers[0].price, Coin::new(900, DENOM));
+
+    // Only the seller can accept an offer for an escrowed art NFT
+    let err = contracts
+        .art_dealer
+        .accept_offer(&mut app, &user2, "art2", &buyer)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .accept_offer(&mut app, &user1, "art2", &buyer)
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art2"), BUYER);
+    assert_eq!(balance(&app, ARTIST), 90);
+    assert_eq!(balance(&app, USER1), 380 + 810);
+    assert!(contracts
+        .art_dealer
+        .query_offers(&mut app, "art2")
+        .unwrap()
+        .offers
+        .is_empty());
+
+    // Escrowed art NFTs can be bought for their price
+    send_to_art_dealer(
+        &mut app,
+        &contracts,
+        &user1,
+        "art3",
+        &ReceiveNftMsg::ListForSale {
+            price: Coin::new(1000, DENOM),
+        },
+    );
+    let err = contracts
+        .art_dealer
+        .buy(&mut app, &buyer, "art3", &coins(999, DENOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::WrongPaymentAmount {});
+    contracts
+        .art_dealer
+        .buy(&mut app, &buyer, "art3", &coins(1000, DENOM))
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art3"), BUYER);
+    assert_eq!(balance(&app, dao.as_str()), 20 + 50);
+    assert_eq!(balance(&app, USER1), 380 + 810 + 950);
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS - 400 - 900 - 1000);
+
+    let err = contracts
+        .art_dealer
+        .buy(&mut app, &buyer, "art3", &coins(1000, DENOM))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotForSale {
+            token_id: "art3".into()
+        }
+    );
+}
+
+#[test]
+fn roles_and_pause() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let admin = Addr::unchecked(ADMIN);
+    let pauser = Addr::unchecked(PAUSER);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+    let buyer = Addr::unchecked(BUYER);
+
+    let contracts = instantiate_contracts(&mut app);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+
+    // Only governance approves dealers
+    let err = contracts
+        .art_dealer
+        .approve_dealer(&mut app, &admin, &dao, 2, 100, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 2, 100, None)
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art1", &user1, None)
+        .unwrap();
+
+    // The admin can replace the pauser but not governance
+    let err = contracts
+        .art_dealer
+        .update_roles(&mut app, &user1, None, None, Some(USER1))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = contracts
+        .art_dealer
+        .update_roles(&mut app, &admin, Some(ADMIN), None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .update_roles(&mut app, &admin, None, None, Some(USER2))
+        .unwrap();
+    let config = contracts.art_dealer.query_config(&mut app).unwrap();
+    assert_eq!(config.governance, governance);
+    assert_eq!(config.admin, Some(admin.clone()));
+    assert_eq!(config.pauser, Some(user2.clone()));
+
+    // Only the current pauser or governance can pause
+    let err = contracts.art_dealer.pause(&mut app, &pauser).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = contracts.art_dealer.pause(&mut app, &admin).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts.art_dealer.pause(&mut app, &user2).unwrap();
+    assert!(contracts.art_dealer.query_config(&mut app).unwrap().paused);
+
+    // Minting and trading stop while paused
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+    let err = contracts
+        .art_dealer
+        .make_offer(&mut app, &buyer, "art1", &coins(100, DENOM))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS);
+
+    let err = contracts.art_dealer.unpause(&mut app, &admin).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts.art_dealer.unpause(&mut app, &governance).unwrap();
+    assert!(!contracts.art_dealer.query_config(&mut app).unwrap().paused);
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap();
+
+    // Governance can hand over all of its roles
+    contracts
+        .art_dealer
+        .update_roles(&mut app, &governance, Some(USER1), Some(USER1), None)
+        .unwrap();
+    let err = contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 2, 100, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = contracts
+        .art_dealer
+        .pause(&mut app, &governance)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &user1, &dao, 2, 100, None)
+        .unwrap();
+}
+
+#[test]
+fn migrate_from_0_14_0_keeps_the_owner_as_governance() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked(OWNER);
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+
+    let legacy_code_id = store_legacy_code(&mut app);
+    let contracts = instantiate_contracts_with_code(&mut app, legacy_code_id, |_| {});
+    let dao = create_dao(&mut app, &contracts, "mydao");
+
+    let raw_config = app
+        .wrap()
+        .query_wasm_raw(contracts.art_dealer.addr(), b"config".as_slice())
+        .unwrap()
+        .unwrap();
+    assert!(String::from_utf8(raw_config).unwrap().contains("\"owner\""));
+    let version = cw2::query_contract_info(&app.wrap(), contracts.art_dealer.addr()).unwrap();
+    assert_eq!(version.version, upgrades::v0_15::FROM_VERSION);
+
+    // The owner of the stored config is read back as governance, the other roles are unset
+    let config = contracts.art_dealer.query_config(&mut app).unwrap();
+    assert_eq!(config.governance, governance);
+    assert_eq!(config.admin, None);
+    assert_eq!(config.pauser, None);
+    assert!(!config.paused);
+    assert_eq!(config.art_nft_address, Some(contracts.art_nft.clone()));
+
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 2, 100, None)
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art1", &user1, None)
+        .unwrap();
+
+    // Only the admin of the contract can migrate it
+    let new_code_id = ArtDealerContract::store_code(&mut app);
+    app.migrate_contract(
+        governance.clone(),
+        contracts.art_dealer.addr().clone(),
+        &MigrateMsg {},
+        new_code_id,
+    )
+    .unwrap_err();
+    contracts
+        .art_dealer
+        .migrate(&mut app, &owner, new_code_id)
+        .unwrap();
+
+    let version = cw2::query_contract_info(&app.wrap(), contracts.art_dealer.addr()).unwrap();
+    assert_eq!(version.contract, "art-dealer");
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+    // The config is stored in the current layout and the approval is kept
+    let raw_config = app
+        .wrap()
+        .query_wasm_raw(contracts.art_dealer.addr(), b"config".as_slice())
+        .unwrap()
+        .unwrap();
+    let raw_config = String::from_utf8(raw_config).unwrap();
+    assert!(raw_config.contains("\"governance\""));
+    assert!(!raw_config.contains("\"owner\""));
+    assert_eq!(
+        contracts
+            .art_dealer
+            .query_config(&mut app)
+            .unwrap()
+            .governance,
+        governance
+    );
+
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap();
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art3", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovedExceeded {});
+
+    // Migrating to the same version again changes nothing
+    contracts
+        .art_dealer
+        .migrate(&mut app, &owner, new_code_id)
+        .unwrap();
+}
+
+#[test]
+fn legacy_art_nft_mints_and_trades_without_royalties() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+    let buyer = Addr::unchecked(BUYER);
+
+    let legacy_art_nft_code_id = store_legacy_art_nft_code(&mut app);
+    let contracts =
+        instantiate_contracts_with(&mut app, |msg| msg.art_nft_code_id = legacy_art_nft_code_id);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 3, 100, None)
+        .unwrap();
+
+    // Metadata without a royalty or content hash is minted in the layout the old code knows
+    contracts
+        .art_dealer
+        .mint_art(
+            &mut app,
+            &dao,
+            "art1",
+            &user1,
+            Some(Metadata {
+                name: Some("Art 1".into()),
+                ..Metadata::default()
+            }),
+        )
+        .unwrap();
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art1"), USER1);
+
+    // The old code rejects royalties
+    app.execute_contract(
+        dao.clone(),
+        contracts.art_dealer.addr().clone(),
+        &ExecuteMsg::MintArt {
+            token_id: "art3".into(),
+            owner: USER1.into(),
+            token_uri: None,
+            metadata: Some(Metadata {
+                royalty_percentage: Some(10),
+                royalty_payment_address: Some(ARTIST.into()),
+                ..Metadata::default()
+            }),
+        },
+        &[],
+    )
+    .unwrap_err();
+
+    // It doesn't answer cw2981 queries, so its art NFTs owe no royalty
+    let royalty = contracts
+        .art_dealer
+        .query_royalty_info(&mut app, "art1", 1000)
+        .unwrap();
+    assert_eq!(royalty.address, "");
+    assert_eq!(royalty.royalty_amount, Uint128::zero());
+
+    send_to_art_dealer(
+        &mut app,
+        &contracts,
+        &user1,
+        "art1",
+        &ReceiveNftMsg::ListForSale {
+            price: Coin::new(1000, DENOM),
+        },
+    );
+    contracts
+        .art_dealer
+        .buy(&mut app, &buyer, "art1", &coins(1000, DENOM))
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art1"), BUYER);
+    assert_eq!(balance(&app, USER1), 1000);
+    assert_eq!(balance(&app, dao.as_str()), 0);
+}
+
+#[test]
+fn signed_vouchers() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+    let user2 = Addr::unchecked(USER2);
+    let buyer = Addr::unchecked(BUYER);
+
+    let contracts = instantiate_contracts(&mut app);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    // The fixed signatures were made for these addresses
+    assert_eq!(contracts.art_dealer.addr().as_str(), "contract1");
+    assert_eq!(dao, "contract4");
+
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 3, 1000, None)
+        .unwrap();
+
+    let priced_voucher = MintVoucher {
+        dao: dao.to_string(),
+        token_id: "art1".into(),
+        token_uri: Some("ipfs://art1".into()),
+        metadata: None,
+        price: Some(Coin::new(1000, DENOM)),
+        expires: 100000,
+        buyer: Some(BUYER.into()),
+    };
+    let free_voucher = MintVoucher {
+        dao: dao.to_string(),
+        token_id: "art2".into(),
+        token_uri: None,
+        metadata: None,
+        price: None,
+        expires: 100000,
+        buyer: None,
+    };
+    // The DAO signs exactly this encoding of the vouchers
+    assert_eq!(to_vec(&priced_voucher).unwrap(), PRICED_VOUCHER.as_bytes());
+    assert_eq!(to_vec(&free_voucher).unwrap(), FREE_VOUCHER.as_bytes());
+    let priced_signature = Binary::from_base64(PRICED_VOUCHER_SIGNATURE).unwrap();
+    let free_signature = Binary::from_base64(FREE_VOUCHER_SIGNATURE).unwrap();
+
+    // Vouchers can only be redeemed once the DAO set its signer
+    let err = contracts
+        .art_dealer
+        .redeem_voucher(
+            &mut app,
+            &buyer,
+            &priced_voucher,
+            &priced_signature,
+            &coins(1000, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::VoucherSignerNotSet {});
+    let err = contracts
+        .art_dealer
+        .set_voucher_signer(
+            &mut app,
+            &user1,
+            Some(Binary::from_base64(VOUCHER_PUBKEY).unwrap()),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .set_voucher_signer(
+            &mut app,
+            &dao,
+            Some(Binary::from_base64(VOUCHER_PUBKEY).unwrap()),
+        )
+        .unwrap();
+
+    // A tampered voucher doesn't match the signature
+    let tampered_voucher = MintVoucher {
+        price: Some(Coin::new(1, DENOM)),
+        ..priced_voucher.clone()
+    };
+    let err = contracts
+        .art_dealer
+        .redeem_voucher(
+            &mut app,
+            &buyer,
+            &tampered_voucher,
+            &priced_signature,
+            &coins(1, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidVoucherSignature {});
+
+    // Only the buyer of the voucher can redeem it
+    let err = contracts
+        .art_dealer
+        .redeem_voucher(&mut app, &user2, &priced_voucher, &priced_signature, &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::VoucherBuyerMismatch {});
+
+    let err = contracts
+        .art_dealer
+        .redeem_voucher(
+            &mut app,
+            &buyer,
+            &priced_voucher,
+            &priced_signature,
+            &coins(999, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::WrongPaymentAmount {});
+    contracts
+        .art_dealer
+        .redeem_voucher(
+            &mut app,
+            &buyer,
+            &priced_voucher,
+            &priced_signature,
+            &coins(1000, DENOM),
+        )
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art1"), BUYER);
+    assert_eq!(balance(&app, dao.as_str()), 1000);
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS - 1000);
+
+    // A redeemed voucher can't be replayed
+    let err = contracts
+        .art_dealer
+        .redeem_voucher(
+            &mut app,
+            &buyer,
+            &priced_voucher,
+            &priced_signature,
+            &coins(1000, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VoucherAlreadyRedeemed {
+            token_id: "art1".into()
+        }
+    );
+
+    // Vouchers without a price or buyer are free for anyone holding them
+    let err = contracts
+        .art_dealer
+        .redeem_voucher(
+            &mut app,
+            &buyer,
+            &free_voucher,
+            &free_signature,
+            &coins(1000, DENOM),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Payment(PaymentError::NonPayable {}));
+    contracts
+        .art_dealer
+        .redeem_voucher(&mut app, &user2, &free_voucher, &free_signature, &[])
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art2"), USER2);
+    assert_eq!(balance(&app, BUYER), BUYER_FUNDS - 1000);
+}
+
+#[test]
+fn batch_mints_count_against_the_approval() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+
+    let contracts = instantiate_contracts(&mut app);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 3, 1000, None)
+        .unwrap();
+
+    let items = |token_ids: &[&str]| -> Vec<MintItem> {
+        token_ids
+            .iter()
+            .map(|token_id| MintItem {
+                token_id: token_id.to_string(),
+                owner: USER1.into(),
+                token_uri: None,
+                metadata: None,
+            })
+            .collect()
+    };
+
+    // The whole batch has to fit into the approval, otherwise nothing is minted
+    let err = contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &dao,
+            &ExecuteMsg::BatchMintArt {
+                items: items(&["art1", "art2", "art3", "art4"]),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovedExceeded {});
+
+    contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &dao,
+            &ExecuteMsg::BatchMintArt {
+                items: items(&["art1", "art2"]),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art2"), USER1);
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &dao)
+        .unwrap()
+        .approval
+        .unwrap();
+    assert_eq!(approval.minted, 2);
+
+    let err = contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &dao,
+            &ExecuteMsg::BatchMintArt {
+                items: items(&["art3", "art4"]),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::ApprovedExceeded {});
+}
+
+#[test]
+fn batch_mint_size_is_limited() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+
+    let contracts = instantiate_contracts(&mut app);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 100, 1000, None)
+        .unwrap();
+
+    let items = |count: usize| -> Vec<MintItem> {
+        (0..count)
+            .map(|i| MintItem {
+                token_id: format!("art{}", i),
+                owner: USER1.into(),
+                token_uri: None,
+                metadata: None,
+            })
+            .collect()
+    };
+
+    let err = contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &dao,
+            &ExecuteMsg::BatchMintArt { items: items(51) },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::BatchTooLarge { max: 50 });
+
+    contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &dao,
+            &ExecuteMsg::BatchMintArt { items: items(50) },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(owner_of(&app, &contracts.art_nft, "art49"), USER1);
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &dao)
+        .unwrap()
+        .approval
+        .unwrap();
+    assert_eq!(approval.minted, 50);
+}
+
+#[test]
+fn collections_are_instantiated_on_the_first_mint() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+
+    let contracts = instantiate_contracts_with(&mut app, |msg| msg.collection_per_dao = true);
+    let dao1 = create_dao(&mut app, &contracts, "mydao");
+    let dao2 = create_dao(&mut app, &contracts, "otherdao");
+    for dao in [&dao1, &dao2] {
+        contracts
+            .art_dealer
+            .approve_dealer(&mut app, &governance, dao, 5, 100, None)
+            .unwrap();
+    }
+
+    // The first mint instantiates the collection of the DAO and mints to it from the reply
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao1, "art1", &user1, None)
+        .unwrap();
+    let collections = contracts
+        .art_dealer
+        .query_collections(&mut app, None, None)
+        .unwrap()
+        .collections;
+    assert_eq!(collections.len(), 1);
+    assert_eq!(collections[0].dao, dao1);
+    let collection1 = collections[0].collection.clone();
+    assert_ne!(collection1, contracts.art_nft);
+
+    let info = contract_info(&app, &collection1);
+    assert_eq!(info.name, "mydao");
+    assert_eq!(info.symbol, "ART");
+    assert_eq!(owner_of(&app, &collection1, "art1"), USER1);
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            &contracts.art_nft,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: "art1".into(),
+                include_expired: None,
+            },
+        )
+        .unwrap_err();
+
+    // Later mints go straight to the existing collection
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao1, "art2", &user1, None)
+        .unwrap();
+    assert_eq!(owner_of(&app, &collection1, "art2"), USER1);
+    assert_eq!(
+        contracts
+            .art_dealer
+            .query_collections(&mut app, None, None)
+            .unwrap()
+            .collections
+            .len(),
+        1
+    );
+    assert_eq!(
+        contracts
+            .art_dealer
+            .query_art_minter(&mut app, "art2")
+            .unwrap()
+            .dao,
+        Some(dao1.clone())
+    );
+
+    // Collections can't be created without a code id to instantiate them from
+    let art_nft_code_id = contracts
+        .art_dealer
+        .query_config(&mut app)
+        .unwrap()
+        .art_nft_code_id;
+    let err = contracts
+        .art_dealer
+        .update_collection_config(&mut app, &user1, Some(0), None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .update_collection_config(&mut app, &governance, Some(0), None)
+        .unwrap();
+    let err = contracts
+        .art_dealer
+        .create_collection(&mut app, &dao2, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::CollectionsDisabled {});
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao2, "art3", &user1, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::CollectionsDisabled {});
+    contracts
+        .art_dealer
+        .update_collection_config(&mut app, &governance, Some(art_nft_code_id), None)
+        .unwrap();
+
+    // Only DAOs can create a collection up front, and only one
+    let err = contracts
+        .art_dealer
+        .create_collection(&mut app, &user1, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contracts
+        .art_dealer
+        .create_collection(&mut app, &dao2, Some("Other Art"), Some("OART"))
+        .unwrap();
+    let err = contracts
+        .art_dealer
+        .create_collection(&mut app, &dao2, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::CollectionExists {});
+
+    let collections = contracts
+        .art_dealer
+        .query_collections(&mut app, Some(&dao1), None)
+        .unwrap()
+        .collections;
+    assert_eq!(collections.len(), 1);
+    assert_eq!(collections[0].dao, dao2);
+    let collection2 = collections[0].collection.clone();
+    let info = contract_info(&app, &collection2);
+    assert_eq!(info.name, "Other Art");
+    assert_eq!(info.symbol, "OART");
+
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao2, "art3", &user1, None)
+        .unwrap();
+    assert_eq!(owner_of(&app, &collection2, "art3"), USER1);
+
+    // Token ids are unique across all collections
+    let err = contracts
+        .art_dealer
+        .mint_art(&mut app, &dao2, "art1", &user1, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TokenIdTaken {
+            token_id: "art1".into()
+        }
+    );
+}
+
+#[test]
+fn art_metadata_is_validated_and_provenance_recorded() {
+    let mut app = mock_app();
+    let governance = Addr::unchecked(GOVERNANCE);
+    let user1 = Addr::unchecked(USER1);
+
+    let contracts = instantiate_contracts(&mut app);
+    let dao = create_dao(&mut app, &contracts, "mydao");
+    contracts
+        .art_dealer
+        .approve_dealer(&mut app, &governance, &dao, 2, 100, Some(7))
+        .unwrap();
+
+    let content_hash = "ab".repeat(32);
+    let attributes = |count: usize| -> Vec<Trait> {
+        (0..count)
+            .map(|i| Trait {
+                display_type: None,
+                trait_type: format!("trait{}", i),
+                value: i.to_string(),
+            })
+            .collect()
+    };
+
+    // URIs have to use one of the supported schemes
+    let err = contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &dao,
+            &ExecuteMsg::MintArt {
+                token_id: "art1".into(),
+                owner: USER1.into(),
+                token_uri: Some("http://example.com/art1.json".into()),
+                metadata: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUri {
+            uri: "http://example.com/art1.json".into()
+        }
+    );
+    let err = contracts
+        .art_dealer
+        .mint_art(
+            &mut app,
+            &dao,
+            "art1",
+            &user1,
+            Some(Metadata {
+                image: Some("ipfs://image".into()),
+                animation_url: Some("ftp://animation".into()),
+                ..Metadata::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUri {
+            uri: "ftp://animation".into()
+        }
+    );
+
+    // Content hashes are hex encoded sha256 hashes
+    for invalid_hash in ["ab".repeat(31), "zz".repeat(32)] {
+        let err = contracts
+            .art_dealer
+            .mint_art(
+                &mut app,
+                &dao,
+                "art1",
+                &user1,
+                Some(Metadata {
+                    content_hash: Some(invalid_hash),
+                    ..Metadata::default()
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidContentHash {});
+    }
+
+    let err = contracts
+        .art_dealer
+        .mint_art(
+            &mut app,
+            &dao,
+            "art1",
+            &user1,
+            Some(Metadata {
+                attributes: Some(attributes(65)),
+                ..Metadata::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyAttributes {});
+
+    let err = contracts
+        .art_dealer
+        .mint_art(
+            &mut app,
+            &dao,
+            "art1",
+            &user1,
+            Some(Metadata {
+                description: Some("a".repeat(16 * 1024)),
+                ..Metadata::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::MetadataTooLarge {});
+
+    // Rejected art doesn't use up the approval
+    let approval = contracts
+        .art_dealer
+        .query_approval(&mut app, &dao)
+        .unwrap()
+        .approval
+        .unwrap();
+    assert_eq!(approval.minted, 0);
+
+    contracts
+        .art_dealer
+        .execute(
+            &mut app,
+            &dao,
+            &ExecuteMsg::MintArt {
+                token_id: "art1".into(),
+                owner: USER1.into(),
+                token_uri: Some("ipfs://art1.json".into()),
+                metadata: Some(Metadata {
+                    image: Some("ar://image".into()),
+                    external_url: Some("https://example.com/art1".into()),
+                    attributes: Some(attributes(64)),
+                    content_hash: Some(content_hash.clone()),
+                    ..Metadata::default()
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+    let art1_height = app.block_info().height;
+
+    app.update_block(next_block);
+    contracts
+        .art_dealer
+        .mint_art(&mut app, &dao, "art2", &user1, None)
+        .unwrap();
+
+    // The provenance links the art NFT to the DAO, the approving proposal and its content
+    let provenance = contracts
+        .art_dealer
+        .query_art_provenance(&mut app, "art1")
+        .unwrap()
+        .provenance;
+    assert_eq!(
+        provenance,
+        Some(Provenance {
+            dao: dao.clone(),
+            height: art1_height,
+            proposal_id: Some(7),
+            content_hash: Some(content_hash),
+            approval_id: approval.id,
+        })
+    );
+    let provenance = contracts
+        .art_dealer
+        .query_art_provenance(&mut app, "art2")
+        .unwrap()
+        .provenance
+        .unwrap();
+    assert_eq!(provenance.height, art1_height + 1);
+    assert_eq!(provenance.content_hash, None);
+    assert_eq!(provenance.approval_id, approval.id);
+
+    assert_eq!(
+        contracts
+            .art_dealer
+            .query_art_provenance(&mut app, "art3")
+            .unwrap()
+            .provenance,
+        None
+    );
+}
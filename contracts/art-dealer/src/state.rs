@@ -1,16 +1,40 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, StdResult, Storage};
+use cw721_metadata_onchain::Metadata;
 use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
-    pub owner: Addr,
+    // Decides on dealer approvals, configs stored before the roles were split call it owner
+    #[serde(alias = "owner")]
+    pub governance: Addr,
+    // Manages the sale config and the pauser
+    #[serde(default)]
+    pub admin: Option<Addr>,
+    // Can pause minting and trading in an emergency
+    #[serde(default)]
+    pub pauser: Option<Addr>,
+    #[serde(default)]
+    pub paused: bool,
     pub identityservice_contract: Addr,
     pub art_nft_address: Option<Addr>,
     pub art_nft_name: String,
     pub art_nft_symbol: String,
+    // Burned NFTs free up the quota of the DAO that minted them
+    #[serde(default)]
+    pub reclaim_quota_on_burn: bool,
+    // Royalty percentage of art NFTs minted without one in their metadata
+    #[serde(default)]
+    pub royalty_percentage: u64,
+    // Code id DAO collections are instantiated from, configs stored before collections were
+    // added have none until it is set with UpdateCollectionConfig
+    #[serde(default)]
+    pub art_nft_code_id: u64,
+    // Creates a collection for every DAO on its first mint instead of minting to art_nft_address
+    #[serde(default)]
+    pub collection_per_dao: bool,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -23,6 +47,184 @@ pub struct Approval {
     pub minted: u64,
     pub burned: u64,
     pub expires: u64,
+    #[serde(default)]
+    pub proposal_id: Option<u64>,
+    // Id of the approve event in the approval history, approvals stored before it was tracked
+    // have 0. Only burns of art NFTs minted under this approval count against `minted`
+    #[serde(default)]
+    pub id: u64,
+}
+
+impl Approval {
+    /// Number of NFTs counted against the approved amount
+    pub fn used(&self, reclaim_quota_on_burn: bool) -> u64 {
+        if reclaim_quota_on_burn {
+            self.minted.saturating_sub(self.burned)
+        } else {
+            self.minted
+        }
+    }
+
+    pub fn is_active(&self, height: u64, reclaim_quota_on_burn: bool) -> bool {
+        height <= self.expires && self.used(reclaim_quota_on_burn) < self.approved
+    }
 }
 
 pub const APPROVALS: Map<&Addr, Approval> = Map::new("approvals");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalAction {
+    Approve {
+        approved: u64,
+        expires: u64,
+        #[serde(default)]
+        proposal_id: Option<u64>,
+    },
+    Revoke {},
+    Mint {
+        token_id: String,
+    },
+    Burn {
+        token_id: String,
+    },
+}
+
+// Entries are only ever added, so the history of each DAO can be audited against
+// the governance proposals that approved it
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ApprovalEvent {
+    pub id: u64,
+    pub dao: Addr,
+    pub action: ApprovalAction,
+    pub height: u64,
+}
+
+// DAO that minted each art NFT (token id)
+pub const TOKEN_MINTERS: Map<&str, Addr> = Map::new("token_minters");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Provenance {
+    pub dao: Addr,
+    pub height: u64,
+    // Governance proposal that approved the DAO to mint
+    pub proposal_id: Option<u64>,
+    pub content_hash: Option<String>,
+    // Id of the approval the art NFT was minted under
+    #[serde(default)]
+    pub approval_id: u64,
+}
+
+pub const TOKEN_PROVENANCE: Map<&str, Provenance> = Map::new("token_provenance");
+
+pub const APPROVAL_EVENT_COUNT: Item<u64> = Item::new("approval_event_count");
+pub const APPROVAL_HISTORY: Map<(&Addr, u64), ApprovalEvent> = Map::new("approval_history");
+
+// Returns the id of the new event
+pub fn record_approval_event(
+    store: &mut dyn Storage,
+    dao: &Addr,
+    action: ApprovalAction,
+    height: u64,
+) -> StdResult<u64> {
+    let id = APPROVAL_EVENT_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    APPROVAL_EVENT_COUNT.save(store, &id)?;
+
+    APPROVAL_HISTORY.save(
+        store,
+        (dao, id),
+        &ApprovalEvent {
+            id,
+            dao: dao.clone(),
+            action,
+            height,
+        },
+    )?;
+    Ok(id)
+}
+
+// Split of primary sale proceeds, the DAO that listed the piece receives what is left
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct SaleConfig {
+    // Denom listings are priced in, sales are disabled until it is set
+    pub denom: Option<String>,
+    // Share of every sale sent to the governance treasury
+    pub treasury_share: Decimal,
+    pub fee_recipients: Vec<FeeRecipient>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipient {
+    pub address: Addr,
+    pub share: Decimal,
+}
+
+pub const SALE_CONFIG: Item<SaleConfig> = Item::new("sale_config");
+
+// Piece a DAO offers for sale, it is minted to the buyer on purchase
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Listing {
+    pub id: u64,
+    pub dao: Addr,
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    pub metadata: Option<Metadata>,
+    pub price: Coin,
+}
+
+pub const LISTING_COUNT: Item<u64> = Item::new("listing_count");
+pub const LISTINGS: Map<u64, Listing> = Map::new("listings");
+
+pub fn next_listing_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id = LISTING_COUNT.may_load(store)?.unwrap_or_default() + 1;
+    LISTING_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+// Art NFT (token id) held in escrow by art-dealer until it is bought or the sale is cancelled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SaleOrder {
+    pub token_id: String,
+    pub seller: Addr,
+    pub price: Coin,
+}
+
+pub const SALE_ORDERS: Map<&str, SaleOrder> = Map::new("sale_orders");
+
+// Offered funds are held in escrow until the offer is accepted or cancelled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Offer {
+    pub token_id: String,
+    pub buyer: Addr,
+    pub price: Coin,
+}
+
+pub const OFFERS: Map<(&str, &Addr), Offer> = Map::new("offers");
+
+// Public key (secp256k1, compressed or uncompressed) each DAO signs its mint vouchers with
+pub const VOUCHER_SIGNERS: Map<&Addr, Binary> = Map::new("voucher_signers");
+
+// Block height each voucher (DAO, token id) was redeemed at, a voucher can only be redeemed once
+pub const REDEEMED_VOUCHERS: Map<(&Addr, &str), u64> = Map::new("redeemed_vouchers");
+
+// cw721 collection of each DAO, DAOs without one mint to art_nft_address
+pub const COLLECTIONS: Map<&Addr, Addr> = Map::new("collections");
+
+// Collection of each art NFT (token id) minted to a DAO collection
+pub const TOKEN_COLLECTIONS: Map<&str, Addr> = Map::new("token_collections");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ArtMint {
+    pub token_id: String,
+    // Mint msg for the collection of the DAO
+    pub msg: Binary,
+}
+
+// Collection being instantiated and the mints waiting for its address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingCollection {
+    pub dao: Addr,
+    pub mints: Vec<ArtMint>,
+}
+
+pub const PENDING_COLLECTION: Item<PendingCollection> = Item::new("pending_collection");
@@ -0,0 +1,23 @@
+use cosmwasm_std::DepsMut;
+
+use crate::contract::CONTRACT_NAME;
+use crate::state::CONFIG;
+use crate::ContractError;
+
+// Last release with a single owner instead of the governance, admin and pauser roles
+pub const FROM_VERSION: &str = "0.14.0";
+pub const TO_VERSION: &str = "0.15.0";
+
+pub fn migrate(deps: DepsMut) -> Result<(), ContractError> {
+    // make sure the contract is being upgraded from the release this module expects
+    cw2::assert_contract_version(deps.storage, CONTRACT_NAME, FROM_VERSION)?;
+
+    // The owner of the stored config becomes governance, the new roles start unset and the
+    // collection settings keep their defaults until governance changes them
+    let config = CONFIG.load(deps.storage)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, TO_VERSION)?;
+
+    Ok(())
+}
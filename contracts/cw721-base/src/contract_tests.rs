@@ -2,7 +2,7 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, CosmosMsg, DepsMut, Empty, Response, StdError, WasmMsg,
+    from_binary, to_binary, Addr, CosmosMsg, DepsMut, Empty, Event, Response, StdError, WasmMsg,
 };
 
 use cw721::{
@@ -114,6 +114,7 @@ fn minting() {
         NftInfoResponse::<Extension> {
             token_uri: Some(token_uri),
             extension: None,
+            frozen: false,
         }
     );
 
@@ -149,6 +150,121 @@ fn minting() {
     assert_eq!(vec![token_id], tokens.tokens);
 }
 
+#[test]
+fn updating_and_freezing_metadata() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let token_uri = "ipfs://broken".to_string();
+
+    let mint_msg = ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("medusa"),
+        token_uri: Some(token_uri),
+        extension: None,
+    };
+    let minter = mock_info(MINTER, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+        .unwrap();
+
+    let fixed_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    let update_msg = ExecuteMsg::UpdateMetadata {
+        token_id: token_id.clone(),
+        token_uri: Some(fixed_uri.clone()),
+        extension: None,
+    };
+
+    // the owner of the token cannot update it
+    let owner = mock_info("medusa", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner, update_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    // minter can update
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter.clone(),
+            update_msg.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(Event::new("metadata_updated").add_attribute("token_id", &token_id))
+            .add_attribute("action", "update_metadata")
+            .add_attribute("minter", MINTER)
+            .add_attribute("token_id", &token_id)
+            .add_attribute("token_uri", &fixed_uri)
+    );
+
+    let info = contract.nft_info(deps.as_ref(), token_id.clone()).unwrap();
+    assert_eq!(
+        info,
+        NftInfoResponse::<Extension> {
+            token_uri: Some(fixed_uri.clone()),
+            extension: None,
+            frozen: false,
+        }
+    );
+
+    // random cannot freeze
+    let freeze_msg = ExecuteMsg::FreezeMetadata {
+        token_id: token_id.clone(),
+    };
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), random, freeze_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Ownership(OwnershipError::NotOwner));
+
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter.clone(),
+            freeze_msg.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(Event::new("metadata_frozen").add_attribute("token_id", &token_id))
+            .add_attribute("action", "freeze_metadata")
+            .add_attribute("minter", MINTER)
+            .add_attribute("token_id", &token_id)
+    );
+
+    let info = contract.nft_info(deps.as_ref(), token_id.clone()).unwrap();
+    assert_eq!(
+        info,
+        NftInfoResponse::<Extension> {
+            token_uri: Some(fixed_uri),
+            extension: None,
+            frozen: true,
+        }
+    );
+
+    // frozen metadata can neither be updated nor frozen again
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), update_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MetadataFrozen {
+            token_id: token_id.clone()
+        }
+    );
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter, freeze_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MetadataFrozen { token_id });
+}
+
 #[test]
 fn test_update_minter() {
     let mut deps = mock_dependencies();
@@ -21,4 +21,7 @@ pub enum ContractError {
 
     #[error("Approval not found for: {spender}")]
     ApprovalNotFound { spender: String },
+
+    #[error("Metadata of token {token_id} is frozen")]
+    MetadataFrozen { token_id: String },
 }
@@ -2,7 +2,9 @@ use cw_ownable::OwnershipError;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use cosmwasm_std::{Binary, CustomMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    Binary, CustomMsg, Deps, DepsMut, Env, Event, MessageInfo, Response, StdResult,
+};
 
 use cw721::{ContractInfoResponse, Cw721Execute, Cw721ReceiveMsg, Expiration};
 
@@ -49,6 +51,12 @@ where
                 token_uri,
                 extension,
             } => self.mint(deps, info, token_id, owner, token_uri, extension),
+            ExecuteMsg::UpdateMetadata {
+                token_id,
+                token_uri,
+                extension,
+            } => self.update_metadata(deps, info, token_id, token_uri, extension),
+            ExecuteMsg::FreezeMetadata { token_id } => self.freeze_metadata(deps, info, token_id),
             ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -102,6 +110,7 @@ where
             approvals: vec![],
             token_uri,
             extension,
+            frozen: false,
         };
         self.tokens
             .update(deps.storage, &token_id, |old| match old {
@@ -118,6 +127,55 @@ where
             .add_attribute("token_id", token_id))
     }
 
+    pub fn update_metadata(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+        token_uri: Option<String>,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        if token.frozen {
+            return Err(ContractError::MetadataFrozen { token_id });
+        }
+        token.token_uri = token_uri;
+        token.extension = extension;
+        self.tokens.save(deps.storage, &token_id, &token)?;
+
+        // Marketplaces and indexers refresh their cached metadata on this event
+        Ok(Response::new()
+            .add_event(Event::new("metadata_updated").add_attribute("token_id", &token_id))
+            .add_attribute("action", "update_metadata")
+            .add_attribute("minter", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("token_uri", token.token_uri.unwrap_or_default()))
+    }
+
+    pub fn freeze_metadata(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        if token.frozen {
+            return Err(ContractError::MetadataFrozen { token_id });
+        }
+        token.frozen = true;
+        self.tokens.save(deps.storage, &token_id, &token)?;
+
+        Ok(Response::new()
+            .add_event(Event::new("metadata_frozen").add_attribute("token_id", &token_id))
+            .add_attribute("action", "freeze_metadata")
+            .add_attribute("minter", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
     pub fn update_ownership(
         deps: DepsMut,
         env: Env,
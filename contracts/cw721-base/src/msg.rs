@@ -64,6 +64,16 @@ pub enum ExecuteMsg<T, E> {
         extension: T,
     },
 
+    /// Replace token_uri and extension of an NFT, can only be called by the contract minter
+    /// until the metadata is frozen
+    UpdateMetadata {
+        token_id: String,
+        token_uri: Option<String>,
+        extension: T,
+    },
+    /// Permanently lock the metadata of an NFT, can only be called by the contract minter
+    FreezeMetadata { token_id: String },
+
     /// Burn an NFT the sender has access to
     Burn { token_id: String },
 
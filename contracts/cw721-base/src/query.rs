@@ -40,6 +40,7 @@ where
         Ok(NftInfoResponse {
             token_uri: info.token_uri,
             extension: info.extension,
+            frozen: info.frozen,
         })
     }
 
@@ -236,6 +237,7 @@ where
             info: NftInfoResponse {
                 token_uri: info.token_uri,
                 extension: info.extension,
+                frozen: info.frozen,
             },
         })
     }
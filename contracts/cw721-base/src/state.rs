@@ -110,6 +110,10 @@ pub struct TokenInfo<T> {
 
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: T,
+
+    /// Once frozen the minter can no longer update token_uri and extension
+    #[serde(default)]
+    pub frozen: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
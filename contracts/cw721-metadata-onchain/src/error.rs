@@ -0,0 +1,17 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Base(#[from] cw721_base::ContractError),
+
+    #[error("Royalty percentage must be between 0 and 100")]
+    InvalidRoyaltyPercentage {},
+
+    #[error("Royalty percentage requires a royalty payment address")]
+    MissingRoyaltyPaymentAddress {},
+}
@@ -1,6 +1,13 @@
+pub mod error;
+pub mod msg;
+pub mod query;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Empty;
-pub use cw721_base::{ContractError, InstantiateMsg, MinterResponse};
+use cosmwasm_std::{Deps, Empty};
+pub use cw721_base::{InstantiateMsg, MinterResponse};
+
+pub use crate::error::ContractError;
+use crate::msg::Cw2981QueryMsg;
 
 // Version info for migration
 const CONTRACT_NAME: &str = "crates.io:cw721-metadata-onchain";
@@ -26,23 +33,58 @@ pub struct Metadata {
     pub background_color: Option<String>,
     pub animation_url: Option<String>,
     pub youtube_url: Option<String>,
+    // Fields added after the first release are left out when empty, so metadata without them
+    // can still be minted on collections running the old code
+    /// Percentage (0 to 100) of every sale price owed to `royalty_payment_address`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_percentage: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_payment_address: Option<String>,
+    /// Hex encoded sha256 hash of the image bytes, pins the content behind the image URI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
 }
 
 pub type Extension = Option<Metadata>;
 
-pub type Cw721MetadataContract<'a> = cw721_base::Cw721Contract<'a, Extension, Empty, Empty, Empty>;
+pub type Cw721MetadataContract<'a> =
+    cw721_base::Cw721Contract<'a, Extension, Empty, Empty, Cw2981QueryMsg>;
 pub type ExecuteMsg = cw721_base::ExecuteMsg<Extension, Empty>;
-pub type QueryMsg = cw721_base::QueryMsg<Empty>;
+pub type QueryMsg = cw721_base::QueryMsg<Cw2981QueryMsg>;
+
+/// A royalty has to be paid to someone and can't exceed the sale price
+pub fn validate_royalties(deps: Deps, extension: &Extension) -> Result<(), ContractError> {
+    let metadata = match extension {
+        Some(metadata) => metadata,
+        None => return Ok(()),
+    };
+
+    if let Some(royalty_payment_address) = &metadata.royalty_payment_address {
+        deps.api.addr_validate(royalty_payment_address)?;
+    }
+    if let Some(royalty_percentage) = metadata.royalty_percentage {
+        if royalty_percentage > 100 {
+            return Err(ContractError::InvalidRoyaltyPercentage {});
+        }
+        if metadata.royalty_payment_address.is_none() {
+            return Err(ContractError::MissingRoyaltyPaymentAddress {});
+        }
+    }
 
-#[cfg(not(feature = "library"))]
+    Ok(())
+}
+
+// Compiled with the library feature too, so other contracts can wrap it in their multitests
 pub mod entry {
     use super::*;
 
+    use crate::query::{check_royalties, query_royalties_info};
+    #[cfg(not(feature = "library"))]
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{to_binary, Binary, DepsMut, Env, MessageInfo, Response, StdResult};
 
     // This makes a conscious choice on the various generics used by the contract
-    #[entry_point]
+    #[cfg_attr(not(feature = "library"), entry_point)]
     pub fn instantiate(
         mut deps: DepsMut,
         env: Env,
@@ -54,19 +96,34 @@ pub mod entry {
         Cw721MetadataContract::default().instantiate(deps.branch(), env, info, msg)
     }
 
-    #[entry_point]
+    #[cfg_attr(not(feature = "library"), entry_point)]
     pub fn execute(
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
-        Cw721MetadataContract::default().execute(deps, env, info, msg)
+        if let ExecuteMsg::Mint { extension, .. } | ExecuteMsg::UpdateMetadata { extension, .. } =
+            &msg
+        {
+            validate_royalties(deps.as_ref(), extension)?;
+        }
+
+        Ok(Cw721MetadataContract::default().execute(deps, env, info, msg)?)
     }
 
-    #[entry_point]
+    #[cfg_attr(not(feature = "library"), entry_point)]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-        Cw721MetadataContract::default().query(deps, env, msg)
+        match msg {
+            QueryMsg::Extension { msg } => match msg {
+                Cw2981QueryMsg::RoyaltyInfo {
+                    token_id,
+                    sale_price,
+                } => to_binary(&query_royalties_info(deps, token_id, sale_price)?),
+                Cw2981QueryMsg::CheckRoyalties {} => to_binary(&check_royalties(deps)?),
+            },
+            _ => Cw721MetadataContract::default().query(deps, env, msg),
+        }
     }
 }
 
@@ -74,7 +131,9 @@ pub mod entry {
 mod tests {
     use super::*;
 
+    use crate::msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, Uint128};
     use cw721::Cw721Query;
 
     const CREATOR: &str = "creator";
@@ -138,4 +197,98 @@ mod tests {
         assert_eq!(res.token_uri, token_uri);
         assert_eq!(res.extension, extension);
     }
+
+    #[test]
+    fn validate_royalty_info() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            minter: CREATOR.to_string(),
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        // royalty above 100 percent is rejected
+        let exec_msg = ExecuteMsg::Mint {
+            token_id: "Enterprise".to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                royalty_percentage: Some(101),
+                royalty_payment_address: Some("jeanluc".to_string()),
+                ..Metadata::default()
+            }),
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidRoyaltyPercentage {});
+
+        // so is a royalty without payment address
+        let exec_msg = ExecuteMsg::Mint {
+            token_id: "Enterprise".to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                royalty_percentage: Some(10),
+                ..Metadata::default()
+            }),
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert_eq!(err, ContractError::MissingRoyaltyPaymentAddress {});
+
+        let exec_msg = ExecuteMsg::Mint {
+            token_id: "Enterprise".to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                royalty_percentage: Some(10),
+                royalty_payment_address: Some("jeanluc".to_string()),
+                ..Metadata::default()
+            }),
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+
+        // tokens without royalty owe none
+        let exec_msg = ExecuteMsg::Mint {
+            token_id: "Voyager".to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
+
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981QueryMsg::RoyaltyInfo {
+                token_id: "Enterprise".to_string(),
+                sale_price: Uint128::new(105),
+            },
+        };
+        let res: RoyaltiesInfoResponse =
+            from_binary(&entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(
+            res,
+            RoyaltiesInfoResponse {
+                address: "jeanluc".to_string(),
+                royalty_amount: Uint128::new(10),
+            }
+        );
+
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981QueryMsg::RoyaltyInfo {
+                token_id: "Voyager".to_string(),
+                sale_price: Uint128::new(105),
+            },
+        };
+        let res: RoyaltiesInfoResponse =
+            from_binary(&entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.royalty_amount, Uint128::zero());
+
+        let query_msg = QueryMsg::Extension {
+            msg: Cw2981QueryMsg::CheckRoyalties {},
+        };
+        let res: CheckRoyaltiesResponse =
+            from_binary(&entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert!(res.royalty_payments);
+    }
 }
@@ -0,0 +1,40 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CustomMsg, Uint128};
+
+/// cw2981 royalty queries, sent as `QueryMsg::Extension { msg }`
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Cw2981QueryMsg {
+    /// Should be called on sale to see if royalties are owed
+    /// by the marketplace selling the NFT.
+    /// See https://eips.ethereum.org/EIPS/eip-2981
+    #[returns(RoyaltiesInfoResponse)]
+    RoyaltyInfo {
+        token_id: String,
+        // the denom of this sale must also be the denom returned by RoyaltiesInfoResponse
+        sale_price: Uint128,
+    },
+    /// Called against contract to determine if this NFT
+    /// implements royalties. Should return a boolean as part of
+    /// CheckRoyaltiesResponse - default can simply be true
+    /// if royalties are implemented at token level
+    #[returns(CheckRoyaltiesResponse)]
+    CheckRoyalties {},
+}
+
+impl CustomMsg for Cw2981QueryMsg {}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    // Note that this must be the same denom as that passed in to RoyaltyInfo
+    // rounding up or down is at the discretion of the implementer
+    pub royalty_amount: Uint128,
+}
+
+/// Shows if the contract implements royalties
+/// if royalty_payments is true, marketplaces should pay them
+#[cw_serde]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
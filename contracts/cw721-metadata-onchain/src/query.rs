@@ -0,0 +1,41 @@
+use cosmwasm_std::{Decimal, Deps, StdResult, Uint128};
+
+use crate::msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse};
+use crate::Cw721MetadataContract;
+
+/// NOTE: default behaviour here is to round down
+/// EIP2981 specifies that the rounding behaviour is at the discretion of the implementer
+pub fn query_royalties_info(
+    deps: Deps,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<RoyaltiesInfoResponse> {
+    let contract = Cw721MetadataContract::default();
+    let token_info = contract.tokens.load(deps.storage, &token_id)?;
+
+    let royalty = token_info.extension.and_then(|metadata| {
+        Some((
+            metadata.royalty_payment_address?,
+            metadata.royalty_percentage?,
+        ))
+    });
+
+    Ok(match royalty {
+        Some((address, percentage)) => RoyaltiesInfoResponse {
+            address,
+            royalty_amount: sale_price * Decimal::percent(percentage),
+        },
+        None => RoyaltiesInfoResponse {
+            address: String::new(),
+            royalty_amount: Uint128::zero(),
+        },
+    })
+}
+
+/// As our default implementation here specifies royalties at token level
+/// and not at contract level, it checks nothing and returns true
+pub fn check_royalties(_deps: Deps) -> StdResult<CheckRoyaltiesResponse> {
+    Ok(CheckRoyaltiesResponse {
+        royalty_payments: true,
+    })
+}
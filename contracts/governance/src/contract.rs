@@ -1,27 +1,50 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, ProposalMsg};
-use crate::state::{Config, CoreSlots, CONFIG, CORE_SLOTS, PROPOSAL_COUNT, WINNING_GRANTS};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, ProposalMsg};
+use crate::state::{
+    Config, CoreSlots, PauseInfo, CONFIG, CORE_SLOTS, PAUSE, PROPOSAL_COUNT, WINNING_GRANTS,
+};
+use crate::upgrades;
 use art_dealer::msg::ExecuteMsg::ApproveDealer;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
-use cw2::set_contract_version;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw_utils::ensure_from_older_version;
 use dao_multisig::msg::QueryMsg::ListVoters as ListDaoVoters;
 use jmes::msg::GovernanceQueryMsg as QueryMsg;
 use jmes::msg::SlotVoteResult;
 
+use identityservice::msg::ExecuteMsg as IdentityserviceExecuteMsg;
 use identityservice::msg::QueryMsg::GetIdentityByOwner;
 use identityservice::state::IdType::Dao;
+use identityservice::state::IdentityStatus;
 
 // Address for burning the proposal fee
 const BURN_ADDRESS: &str = "jmes1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqf5laz2";
 
 // version info for migration info
-const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // Default pagination constants
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
 
+// Maximum number of proposals a proposal can depend on
+const MAX_DEPENDENCIES: usize = 10;
+
+// A pause ends automatically after 7 days so governance can't be frozen indefinitely
+const MAX_PAUSE_DURATION: u64 = 604_800;
+
+// Seconds the guardian has to wait after a pause ended, unless governance set another cooldown
+const DEFAULT_GUARDIAN_COOLDOWN: u64 = MAX_PAUSE_DURATION;
+
+// Number of core slot DAOs that have to agree to pause or unpause governance
+const CORE_SLOT_PAUSE_QUORUM: usize = 2;
+
+// Reply to a failed DAO fee refund, a refund must never block concluding a proposal
+const REFUND_DAO_FEE_REPLY_ID: u64 = 1;
+
 // Initial Distribution Period in blocks
 // Winning grants are paid out after this period ends
 const IDP_BLOCKS: u64 = 483_840;
@@ -34,6 +57,10 @@ pub fn instantiate(
 ) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let owner_addr = deps.api.addr_validate(&msg.owner)?;
+    let guardian = msg
+        .guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
 
     let config = Config {
         owner: Some(owner_addr),
@@ -44,6 +71,8 @@ pub fn instantiate(
         period_start_epoch: msg.period_start_epoch,                     // 1660000000,
         posting_period_length: msg.posting_period_length,               // 300000,
         voting_period_length: msg.voting_period_length,                 // 606864,
+        guardian,
+        guardian_cooldown: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -59,10 +88,42 @@ pub fn instantiate(
 
     WINNING_GRANTS.save(deps.storage, &vec![])?;
 
+    PAUSE.save(deps.storage, &PauseInfo::default())?;
+
     PROPOSAL_COUNT.save(deps.storage, &(0 as u64))?;
     Ok(Response::new())
 }
 
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = get_contract_version(deps.storage)?.version;
+
+    // Upgrade the state release by release, every upgrade asserts the version it starts from
+    // and bumps the stored version to the release it migrates to
+    if stored_version == upgrades::v0_2::FROM_VERSION {
+        upgrades::v0_2::migrate(deps.branch())?;
+    }
+
+    // Make sure the correct contract is being upgraded and that it's not a downgrade
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored_version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // The DAO keeps its fee pending, it can still be swept after the refund period
+        REFUND_DAO_FEE_REPLY_ID => Ok(Response::new()
+            .add_attribute("action", "refund_dao_fee_failed")
+            .add_attribute("error", msg.result.unwrap_err())),
+        _ => Err(ContractError::StdError(StdError::generic_err(
+            "Unknown reply id.",
+        ))),
+    }
+}
+
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     use jmes::msg::GovernanceQueryMsg::*;
 
@@ -83,6 +144,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         )?),
         CoreSlots {} => to_binary(&query::core_slots(deps, env)?),
         WinningGrants {} => to_binary(&query::winning_grants(deps, env)?),
+        PauseInfo {} => to_binary(&query::pause_info(deps, env)?),
     }
 }
 
@@ -94,6 +156,14 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     use ExecuteMsg::*;
 
+    // Proposals, votes and conclusions are stopped while governance is paused
+    if matches!(msg, Propose(_) | Vote { .. } | Conclude { .. }) {
+        let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+        if pause.is_paused(&env) {
+            return Err(ContractError::Paused {});
+        }
+    }
+
     match msg {
         Propose(proposal_msg) => exec::proposal(deps, env, info, proposal_msg),
         Vote { id, vote } => exec::vote(deps, env, info, id, vote),
@@ -105,11 +175,15 @@ pub fn execute(
             art_dealer,
             identityservice,
         } => exec::set_contract(deps, env, info, art_dealer, identityservice),
+        Pause {} => exec::pause(deps, env, info),
+        Unpause {} => exec::unpause(deps, env, info),
+        SetGuardian { guardian } => exec::set_guardian(deps, env, info, guardian),
+        SetGuardianCooldown { seconds } => exec::set_guardian_cooldown(deps, env, info, seconds),
     }
 }
 
 mod exec {
-    use cosmwasm_std::{BankMsg, Coin, CosmosMsg, Decimal, Uint128, WasmMsg};
+    use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, Decimal, SubMsg, Uint128, WasmMsg};
     use cw3::VoterListResponse;
     use dao_multisig::msg::ConfigResponse;
     use identityservice::msg::GetIdentityByOwnerResponse;
@@ -118,7 +192,7 @@ mod exec {
     use super::*;
 
     use crate::contract::query::period_info;
-    use crate::msg::{CoreSlot, Feature, PeriodInfoResponse, ProposalPeriod};
+    use crate::msg::{CoreSlot, Feature, PeriodInfoResponse, ProposalAction, ProposalPeriod};
     use crate::state::{
         proposals, Proposal, ProposalType,
         VoteOption::{self, *},
@@ -142,10 +216,13 @@ mod exec {
             },
         )?;
 
-        let maybe_identity = maybe_identity_resp.identity;
+        let identity = match maybe_identity_resp.identity {
+            Some(identity) if identity.id_type == Dao => identity,
+            _ => return Err(ContractError::Unauthorized {}),
+        };
 
-        if maybe_identity.is_none() || maybe_identity.unwrap().id_type != Dao {
-            return Err(ContractError::Unauthorized {});
+        if identity.status == IdentityStatus::Dissolved {
+            return Err(ContractError::DaoDissolved {});
         }
 
         // Only during a posting period can new proposals be posted
@@ -168,11 +245,14 @@ mod exec {
             });
         }
 
+        validate_dependencies(deps.as_ref(), &proposal_msg)?;
+
         match proposal_msg {
             ProposalMsg::TextProposal {
                 title,
                 description,
                 funding,
+                depends_on,
             } => text_proposal(
                 deps,
                 info,
@@ -183,12 +263,14 @@ mod exec {
                 title,
                 description,
                 funding,
+                depends_on,
             ),
             ProposalMsg::RequestFeature {
                 title,
                 description,
                 funding,
                 feature,
+                depends_on,
             } => request_feature(
                 deps,
                 info,
@@ -200,12 +282,14 @@ mod exec {
                 description,
                 funding,
                 feature,
+                depends_on,
             ),
 
             ProposalMsg::Improvement {
                 title,
                 description,
                 msgs,
+                depends_on,
             } => improvement(
                 deps,
                 info,
@@ -216,12 +300,31 @@ mod exec {
                 title,
                 description,
                 msgs,
+                depends_on,
+            ),
+            ProposalMsg::Action {
+                title,
+                description,
+                actions,
+                depends_on,
+            } => action(
+                deps,
+                info,
+                env,
+                config,
+                period_info,
+                deposit_amount,
+                title,
+                description,
+                actions,
+                depends_on,
             ),
             ProposalMsg::CoreSlot {
                 title,
                 description,
                 funding,
                 slot,
+                depends_on,
             } => core_slot(
                 deps,
                 info,
@@ -233,11 +336,13 @@ mod exec {
                 description,
                 funding,
                 slot,
+                depends_on,
             ),
             ProposalMsg::RevokeProposal {
                 title,
                 description,
                 revoke_proposal_id,
+                depends_on,
             } => revoke_core_slot(
                 deps,
                 info,
@@ -248,10 +353,48 @@ mod exec {
                 title,
                 description,
                 revoke_proposal_id,
+                depends_on,
             ),
         }
     }
 
+    // Dependencies must be existing proposals that can still pass
+    fn validate_dependencies(deps: Deps, proposal_msg: &ProposalMsg) -> Result<(), ContractError> {
+        let depends_on = proposal_msg.depends_on();
+
+        if depends_on.len() > MAX_DEPENDENCIES {
+            return Err(ContractError::ProposalNotValid {
+                error: format!("Too many dependencies (max {})!", MAX_DEPENDENCIES),
+            });
+        }
+
+        for (i, dependency_id) in depends_on.iter().enumerate() {
+            if depends_on[..i].contains(dependency_id) {
+                return Err(ContractError::ProposalNotValid {
+                    error: format!("Duplicate dependency {}!", dependency_id),
+                });
+            }
+
+            let dependency = proposals().may_load(deps.storage, *dependency_id)?.ok_or(
+                ContractError::ProposalNotValid {
+                    error: format!("Dependency {} does not exist!", dependency_id),
+                },
+            )?;
+
+            if matches!(
+                dependency.concluded_status,
+                Some(ProposalStatus::ExpiredConcluded)
+                    | Some(ProposalStatus::DependencyFailedConcluded)
+            ) {
+                return Err(ContractError::ProposalNotValid {
+                    error: format!("Dependency {} did not pass!", dependency_id),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn text_proposal(
         deps: DepsMut,
         info: MessageInfo,
@@ -262,6 +405,7 @@ mod exec {
         title: String,
         description: String,
         funding: Option<Funding>,
+        depends_on: Vec<u64>,
     ) -> Result<Response, ContractError> {
         let id = Proposal::next_id(deps.storage)?;
         let proposal = Proposal {
@@ -284,6 +428,7 @@ mod exec {
             concluded_coins_total: None,
             funding,
             msgs: None,
+            depends_on,
         };
 
         proposal.validate()?;
@@ -316,7 +461,10 @@ mod exec {
         description: String,
         funding: Funding,
         feature: Feature,
+        depends_on: Vec<u64>,
     ) -> Result<Response, ContractError> {
+        let id = Proposal::next_id(deps.storage)?;
+
         let msg = match feature {
             Feature::ArtDealer { approved } => CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: config.art_dealer_addr.unwrap().to_string(),
@@ -324,12 +472,11 @@ mod exec {
                     dao: info.sender.clone(),
                     approved,
                     duration: funding.duration_in_blocks,
+                    proposal_id: Some(id),
                 })?,
                 funds: vec![],
             }),
         };
-
-        let id = Proposal::next_id(deps.storage)?;
         let proposal = Proposal {
             id,
             dao: info.sender,
@@ -350,6 +497,7 @@ mod exec {
             concluded_coins_total: None,
             funding: Some(funding),
             msgs: Some(vec![msg]),
+            depends_on,
         };
 
         proposal.validate()?;
@@ -381,6 +529,7 @@ mod exec {
         title: String,
         description: String,
         msgs: Vec<CosmosMsg>,
+        depends_on: Vec<u64>,
     ) -> Result<Response, ContractError> {
         let core_slots = CORE_SLOTS.load(deps.storage)?;
 
@@ -412,6 +561,80 @@ mod exec {
             concluded_coins_total: None,
             funding: None,
             msgs: Some(msgs),
+            depends_on,
+        };
+
+        proposal.validate()?;
+
+        proposals().save(deps.storage, id, &proposal)?;
+
+        // Attach bank message to send the deposit amount to the burn address
+        let burn_address = deps.api.addr_validate(BURN_ADDRESS)?;
+        let burn_msg = BankMsg::Send {
+            to_address: burn_address.to_string(),
+            amount: vec![Coin {
+                denom: "ujmes".to_string(),
+                amount: deposit_amount,
+            }],
+        };
+
+        Ok(Response::new()
+            .add_message(burn_msg)
+            .add_attribute("gov_proposal_id", proposal.id.to_string()))
+    }
+
+    pub fn action(
+        deps: DepsMut,
+        info: MessageInfo,
+        env: Env,
+        config: Config,
+        period_info: PeriodInfoResponse,
+        deposit_amount: Uint128,
+        title: String,
+        description: String,
+        actions: Vec<ProposalAction>,
+        depends_on: Vec<u64>,
+    ) -> Result<Response, ContractError> {
+        let core_slots = CORE_SLOTS.load(deps.storage)?;
+
+        // Like improvements, actions can only be submitted by the CoreTech DAO
+        if core_slots.core_tech.map(|s| s.dao) != Some(info.sender.clone()) {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        if actions.is_empty() {
+            return Err(ContractError::ProposalNotValid {
+                error: "No actions!".into(),
+            });
+        }
+
+        let msgs = actions
+            .iter()
+            .map(|action| action_msg(deps.as_ref(), &config, action))
+            .collect::<Result<Vec<CosmosMsg>, ContractError>>()?;
+
+        let id = Proposal::next_id(deps.storage)?;
+        let proposal = Proposal {
+            id,
+            dao: info.sender,
+            title,
+            description,
+            prop_type: ProposalType::Action(actions),
+            coins_no: Uint128::zero(),
+            coins_yes: Uint128::zero(),
+            yes_voters: Vec::new(),
+            no_voters: Vec::new(),
+            deposit_amount,
+            start_block: env.block.height, // used for voting coin lookup
+            posting_start: period_info.current_posting_start,
+            voting_start: period_info.current_voting_start,
+            voting_end: period_info.current_voting_end,
+            concluded_at_height: None,
+            concluded_status: None,
+            concluded_coins_total: None,
+            funding: None,
+            msgs: Some(msgs),
+            depends_on,
         };
 
         proposal.validate()?;
@@ -433,6 +656,66 @@ mod exec {
             .add_attribute("gov_proposal_id", proposal.id.to_string()))
     }
 
+    // Validates an action and turns it into the msg that is executed when the proposal passes
+    fn action_msg(
+        deps: Deps,
+        config: &Config,
+        action: &ProposalAction,
+    ) -> Result<CosmosMsg, ContractError> {
+        let msg = match action.clone() {
+            ProposalAction::MigrateContract {
+                contract,
+                code_id,
+                msg,
+            } => CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr: deps.api.addr_validate(&contract)?.to_string(),
+                new_code_id: code_id,
+                msg,
+            }),
+            ProposalAction::UpdateContractAdmin { contract, admin } => {
+                CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                    contract_addr: deps.api.addr_validate(&contract)?.to_string(),
+                    admin: deps.api.addr_validate(&admin)?.to_string(),
+                })
+            }
+            ProposalAction::BankSend { to_address, amount } => {
+                if amount.is_empty() || amount.iter().any(|coin| coin.amount.is_zero()) {
+                    return Err(ContractError::ProposalNotValid {
+                        error: "BankSend amount must not be zero!".into(),
+                    });
+                }
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: deps.api.addr_validate(&to_address)?.to_string(),
+                    amount,
+                })
+            }
+            ProposalAction::ExecuteOnDao { dao, msg, funds } => {
+                let dao = deps.api.addr_validate(&dao)?;
+
+                // The target has to be a registered DAO
+                let identity: GetIdentityByOwnerResponse = deps.querier.query_wasm_smart(
+                    config.identityservice_addr.clone().unwrap(),
+                    &GetIdentityByOwner {
+                        owner: dao.to_string(),
+                    },
+                )?;
+                if identity.identity.map(|i| i.id_type) != Some(Dao) {
+                    return Err(ContractError::ProposalNotValid {
+                        error: format!("{} is not a DAO!", dao),
+                    });
+                }
+
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: dao.to_string(),
+                    msg,
+                    funds,
+                })
+            }
+        };
+
+        Ok(msg)
+    }
+
     pub fn core_slot(
         deps: DepsMut,
         info: MessageInfo,
@@ -444,6 +727,7 @@ mod exec {
         description: String,
         funding: Funding,
         slot: CoreSlot,
+        depends_on: Vec<u64>,
     ) -> Result<Response, ContractError> {
         let dao = info.sender.clone();
 
@@ -549,6 +833,7 @@ mod exec {
                 msg: to_binary(&ExecuteMsg::SetCoreSlot { proposal_id: id })?,
                 funds: vec![],
             })]),
+            depends_on,
         };
 
         proposal.validate()?;
@@ -642,17 +927,37 @@ mod exec {
             return Err(ContractError::ProposalAlreadyConcluded {});
         }
 
+        // Dependencies have to be concluded first so their final status is known
+        let mut dependencies_met = true;
+        for dependency_id in proposal.depends_on.iter() {
+            let dependency = proposals().load(deps.storage, *dependency_id)?;
+            match dependency.concluded_status {
+                Some(ProposalStatus::SuccessConcluded) => {}
+                Some(_) => dependencies_met = false,
+                None => {
+                    return Err(ContractError::DependencyNotConcluded { id: *dependency_id });
+                }
+            }
+        }
+
         proposal.update_coins_total(&deps.querier);
         proposal.set_concluded_status(
             &deps.querier,
             env.clone(),
             config.proposal_required_percentage,
         );
+        // A proposal that passed but depends on one that didn't is concluded without any effect,
+        // which also fails the proposals depending on it
+        if !dependencies_met && proposal.concluded_status == Some(ProposalStatus::SuccessConcluded)
+        {
+            proposal.concluded_status = Some(ProposalStatus::DependencyFailedConcluded);
+        }
         proposal.concluded_at_height = Some(env.block.height);
 
         proposals().save(deps.storage, id, &proposal)?;
 
         let mut msgs: Vec<CosmosMsg> = vec![];
+        let mut submsgs: Vec<SubMsg> = vec![];
 
         let mut winning_grants = WINNING_GRANTS.load(deps.storage)?;
 
@@ -660,11 +965,26 @@ mod exec {
         winning_grants.retain(|grant| grant.expire_at_height >= env.clone().block.height);
 
         // On proposal success, add winning_grant, process funding proposal and execute attached msgs
-        if proposal.concluded_status.unwrap() == ProposalStatus::SuccessConcluded {
+        if proposal.concluded_status.clone().unwrap() == ProposalStatus::SuccessConcluded {
             if proposal.msgs.is_some() {
                 msgs.extend(proposal.msgs.unwrap());
             }
 
+            // The DAO registration fee is refunded after the DAO's first successful proposal,
+            // the identityservice ignores any later refunds and a failed refund is only reported
+            if let Some(identityservice_addr) = config.identityservice_addr.clone() {
+                submsgs.push(SubMsg::reply_on_error(
+                    WasmMsg::Execute {
+                        contract_addr: identityservice_addr.to_string(),
+                        msg: to_binary(&IdentityserviceExecuteMsg::RefundDaoFee {
+                            dao: proposal.dao.to_string(),
+                        })?,
+                        funds: vec![],
+                    },
+                    REFUND_DAO_FEE_REPLY_ID,
+                ));
+            }
+
             let mut max_cap = 125u64; // 12.5%
 
             let core_slots = CORE_SLOTS.load(deps.storage)?;
@@ -710,7 +1030,10 @@ mod exec {
         // Finally save winning grants after housekeeping and adding the new funding grant
         WINNING_GRANTS.save(deps.storage, &winning_grants)?;
 
-        Ok(Response::new().add_messages(msgs))
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_submessages(submsgs)
+            .add_attribute("dependencies_met", dependencies_met.to_string()))
     }
 
     pub fn resign_core_slot(
@@ -762,6 +1085,7 @@ mod exec {
         title: String,
         description: String,
         revoke_proposal_id: u64,
+        depends_on: Vec<u64>,
     ) -> Result<Response, ContractError> {
         let dao = info.sender.clone();
 
@@ -790,6 +1114,7 @@ mod exec {
                 msg: to_binary(&ExecuteMsg::UnsetCoreSlot { proposal_id: id })?,
                 funds: vec![],
             })]),
+            depends_on,
         };
 
         println!("\n\nproposal {:?}", proposal);
@@ -1108,6 +1433,192 @@ mod exec {
         println!("\n\n config {:?}", config);
         Ok(Response::new())
     }
+
+    // Core slot DAOs whose slot is still funded can vote on pausing governance
+    fn is_core_slot_dao(deps: Deps, env: &Env, addr: &Addr) -> StdResult<bool> {
+        let core_slots = CORE_SLOTS.load(deps.storage)?;
+
+        Ok(
+            [core_slots.brand, core_slots.creative, core_slots.core_tech]
+                .into_iter()
+                .flatten()
+                .any(|slot| &slot.dao == addr && slot.proposal_funding_end >= env.block.height),
+        )
+    }
+
+    // Only votes of DAOs that still hold a core slot count
+    pub(super) fn retain_core_slot_votes(
+        deps: Deps,
+        env: &Env,
+        votes: &mut Vec<Addr>,
+    ) -> StdResult<()> {
+        let mut current = Vec::with_capacity(votes.len());
+        for vote in votes.drain(..) {
+            if is_core_slot_dao(deps, env, &vote)? {
+                current.push(vote);
+            }
+        }
+        *votes = current;
+        Ok(())
+    }
+
+    // A pause that ran out on its own ends like a lifted one, without any leftover votes
+    pub(super) fn end_lapsed_pause(pause: &mut PauseInfo, env: &Env) {
+        if pause.paused_until.is_some() && !pause.is_paused(env) {
+            pause.paused_until = None;
+            pause.guardian_paused = false;
+            pause.pause_votes = vec![];
+            pause.unpause_votes = vec![];
+        }
+    }
+
+    pub fn pause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+
+        if pause.is_paused(&env) {
+            return Err(ContractError::Paused {});
+        }
+        end_lapsed_pause(&mut pause, &env);
+
+        let now = env.block.time.seconds();
+
+        if config.guardian == Some(info.sender.clone()) {
+            // The guardian has to wait for the cooldown so it can't chain pauses, it is counted
+            // from the end of the pause and moved up if the pause is lifted early
+            if now < pause.guardian_cooldown_until {
+                return Err(ContractError::GuardianCooldown {
+                    until: pause.guardian_cooldown_until,
+                });
+            }
+            pause.guardian_cooldown_until = now
+                + MAX_PAUSE_DURATION
+                + config
+                    .guardian_cooldown
+                    .unwrap_or(DEFAULT_GUARDIAN_COOLDOWN);
+            pause.guardian_paused = true;
+        } else if is_core_slot_dao(deps.as_ref(), &env, &info.sender)? {
+            retain_core_slot_votes(deps.as_ref(), &env, &mut pause.pause_votes)?;
+            if !pause.pause_votes.contains(&info.sender) {
+                pause.pause_votes.push(info.sender.clone());
+            }
+
+            if pause.pause_votes.len() < CORE_SLOT_PAUSE_QUORUM {
+                PAUSE.save(deps.storage, &pause)?;
+                return Ok(Response::new()
+                    .add_attribute("action", "vote_pause")
+                    .add_attribute("sender", info.sender));
+            }
+        } else {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        pause.paused_until = Some(now + MAX_PAUSE_DURATION);
+        pause.pause_votes = vec![];
+        pause.unpause_votes = vec![];
+
+        PAUSE.save(deps.storage, &pause)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "pause")
+            .add_attribute("sender", info.sender)
+            .add_attribute("paused_until", (now + MAX_PAUSE_DURATION).to_string()))
+    }
+
+    pub fn unpause(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+
+        if !pause.is_paused(&env) {
+            return Err(ContractError::NotPaused {});
+        }
+
+        // The guardian can only lift its own pause, any other pause needs a core slot quorum
+        if config.guardian != Some(info.sender.clone()) || !pause.guardian_paused {
+            if !is_core_slot_dao(deps.as_ref(), &env, &info.sender)? {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            retain_core_slot_votes(deps.as_ref(), &env, &mut pause.unpause_votes)?;
+            if !pause.unpause_votes.contains(&info.sender) {
+                pause.unpause_votes.push(info.sender.clone());
+            }
+
+            if pause.unpause_votes.len() < CORE_SLOT_PAUSE_QUORUM {
+                PAUSE.save(deps.storage, &pause)?;
+                return Ok(Response::new()
+                    .add_attribute("action", "vote_unpause")
+                    .add_attribute("sender", info.sender));
+            }
+        }
+
+        // The cooldown of a guardian pause starts when the pause ends
+        if pause.guardian_paused {
+            pause.guardian_cooldown_until = env.block.time.seconds()
+                + config
+                    .guardian_cooldown
+                    .unwrap_or(DEFAULT_GUARDIAN_COOLDOWN);
+        }
+
+        pause.paused_until = None;
+        pause.guardian_paused = false;
+        pause.pause_votes = vec![];
+        pause.unpause_votes = vec![];
+
+        PAUSE.save(deps.storage, &pause)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "unpause")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn set_guardian(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        guardian: Option<String>,
+    ) -> Result<Response, ContractError> {
+        // Only the governance contract itself can change the guardian
+        if info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let mut config = CONFIG.load(deps.storage)?;
+        config.guardian = guardian
+            .map(|guardian| deps.api.addr_validate(&guardian))
+            .transpose()?;
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_guardian")
+            .add_attribute(
+                "guardian",
+                config
+                    .guardian
+                    .map(|guardian| guardian.to_string())
+                    .unwrap_or_default(),
+            ))
+    }
+
+    pub fn set_guardian_cooldown(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        seconds: u64,
+    ) -> Result<Response, ContractError> {
+        // Only the governance contract itself can change the cooldown
+        if info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let mut config = CONFIG.load(deps.storage)?;
+        config.guardian_cooldown = Some(seconds);
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_guardian_cooldown")
+            .add_attribute("seconds", seconds.to_string()))
+    }
 }
 
 mod query {
@@ -1117,7 +1628,7 @@ mod query {
     use cw_storage_plus::Bound;
 
     use crate::msg::{
-        PeriodInfoResponse, ProposalPeriod, ProposalResponse, ProposalsResponse,
+        PauseInfoResponse, PeriodInfoResponse, ProposalPeriod, ProposalResponse, ProposalsResponse,
         WinningGrantsResponse,
     };
     use crate::state::{proposals, PROPOSAL_COUNT};
@@ -1217,6 +1728,23 @@ mod query {
         Ok(WinningGrantsResponse { winning_grants })
     }
 
+    pub fn pause_info(deps: Deps, env: Env) -> StdResult<PauseInfoResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+        exec::end_lapsed_pause(&mut pause, &env);
+        exec::retain_core_slot_votes(deps, &env, &mut pause.pause_votes)?;
+        exec::retain_core_slot_votes(deps, &env, &mut pause.unpause_votes)?;
+
+        Ok(PauseInfoResponse {
+            paused: pause.is_paused(&env),
+            paused_until: pause.paused_until,
+            guardian: config.guardian,
+            guardian_cooldown_until: pause.guardian_cooldown_until,
+            pause_votes: pause.pause_votes,
+            unpause_votes: pause.unpause_votes,
+        })
+    }
+
     pub fn proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
         let proposal = proposals().load(deps.storage, id)?;
         let config = CONFIG.load(deps.storage)?;
@@ -1245,6 +1773,8 @@ mod query {
                 config.proposal_required_percentage,
             ),
             coins_total: proposal.query_coins_total(&deps.querier),
+            depends_on: proposal.depends_on.clone(),
+            summary: proposal.summary(),
         })
     }
 
@@ -1293,6 +1823,8 @@ mod query {
                         config.proposal_required_percentage,
                     ),
                     coins_total: proposal.query_coins_total(&deps.querier),
+                    depends_on: proposal.depends_on.clone(),
+                    summary: proposal.summary(),
                 })
             })
             .collect::<StdResult<Vec<_>>>()?;
@@ -6,6 +6,8 @@ use thiserror::Error;
 pub enum ContractError {
     #[error("{0}")]
     StdError(#[from] StdError),
+    #[error("{0}")]
+    Version(#[from] cw2::VersionError),
     #[error("Unauthorized")]
     Unauthorized {},
     #[error("WrongCoreTeamMemberCount (Core Team must have between {min} and {max} members)!")]
@@ -32,6 +34,8 @@ pub enum ContractError {
     ProposalNotValid { error: String },
     #[error("ProposalAlreadyConcluded")]
     ProposalAlreadyConcluded {},
+    #[error("DependencyNotConcluded (Proposal {id} must be concluded first)!")]
+    DependencyNotConcluded { id: u64 },
     #[error("ProposalVotingEnded")]
     ProposalVotingEnded {},
     #[error("InvalidProposalType")]
@@ -40,6 +44,14 @@ pub enum ContractError {
     WrongDao {},
     #[error("AlreadyHoldingCoreSlot")]
     AlreadyHoldingCoreSlot {},
+    #[error("Paused")]
+    Paused {},
+    #[error("NotPaused")]
+    NotPaused {},
+    #[error("GuardianCooldown (The guardian can pause again at {until})!")]
+    GuardianCooldown { until: u64 },
+    #[error("DaoDissolved")]
+    DaoDissolved {},
 }
 
 impl From<OverflowError> for ContractError {
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use cosmwasm_std::{Addr, CosmosMsg, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +19,8 @@ pub struct InstantiateMsg {
     pub posting_period_length: u64,
     // Length in seconds of the posting period, e.g.  606864 for ~ 1 Week (year/52)
     pub voting_period_length: u64,
+    // Address that can pause governance in an emergency
+    pub guardian: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
@@ -51,6 +53,17 @@ pub enum ExecuteMsg {
         slot: CoreSlot,
         note: String, // Can be used to explain why the dao is resigning, is only added as an attribute to the events
     },
+    // Pauses proposals, votes and conclusions, either by the guardian or a quorum of core slot DAOs
+    Pause {},
+    Unpause {},
+    // Can only be called by the governance contract itself, i.e. through a proposal
+    SetGuardian {
+        guardian: Option<String>,
+    },
+    // Can only be called by the governance contract itself, i.e. through a proposal
+    SetGuardianCooldown {
+        seconds: u64,
+    },
     // RemoveFeature { feature: Feature },
 
     // RequestCoreSlot { core_slot: CoreSlot },
@@ -61,6 +74,8 @@ pub enum ExecuteMsg {
 }
 
 /// This structure stores the parameters for the different proposal types
+/// `depends_on` lists the ids of earlier proposals that must have passed before the msgs
+/// of this proposal are executed and its funding is granted
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ProposalMsg {
@@ -68,30 +83,131 @@ pub enum ProposalMsg {
         title: String,
         description: String,
         funding: Option<Funding>,
+        #[serde(default)]
+        depends_on: Vec<u64>,
     },
     RequestFeature {
         title: String,
         description: String,
         funding: Funding,
         feature: Feature,
+        #[serde(default)]
+        depends_on: Vec<u64>,
     },
     Improvement {
         title: String,
         description: String,
         msgs: Vec<CosmosMsg>,
+        #[serde(default)]
+        depends_on: Vec<u64>,
+    },
+    Action {
+        title: String,
+        description: String,
+        actions: Vec<ProposalAction>,
+        #[serde(default)]
+        depends_on: Vec<u64>,
     },
     CoreSlot {
         title: String,
         description: String,
         funding: Funding,
         slot: CoreSlot,
+        #[serde(default)]
+        depends_on: Vec<u64>,
     },
     RevokeProposal {
         title: String,
         description: String,
         revoke_proposal_id: u64,
+        #[serde(default)]
+        depends_on: Vec<u64>,
     },
 }
+
+impl ProposalMsg {
+    pub fn depends_on(&self) -> &[u64] {
+        match self {
+            ProposalMsg::TextProposal { depends_on, .. }
+            | ProposalMsg::RequestFeature { depends_on, .. }
+            | ProposalMsg::Improvement { depends_on, .. }
+            | ProposalMsg::Action { depends_on, .. }
+            | ProposalMsg::CoreSlot { depends_on, .. }
+            | ProposalMsg::RevokeProposal { depends_on, .. } => depends_on.as_slice(),
+        }
+    }
+}
+
+/// Typed alternatives to raw msgs, the governance contract turns them into msgs itself
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalAction {
+    // The governance contract has to be the admin of the contract
+    MigrateContract {
+        contract: String,
+        code_id: u64,
+        msg: Binary,
+    },
+    UpdateContractAdmin {
+        contract: String,
+        admin: String,
+    },
+    // Paid from the governance contract balance
+    BankSend {
+        to_address: String,
+        amount: Vec<Coin>,
+    },
+    ExecuteOnDao {
+        dao: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    },
+}
+
+impl fmt::Display for ProposalAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProposalAction::MigrateContract {
+                contract,
+                code_id,
+                msg,
+            } => write!(
+                f,
+                "migrate contract {} to code id {} with {}",
+                contract,
+                code_id,
+                readable_msg(msg)
+            ),
+            ProposalAction::UpdateContractAdmin { contract, admin } => {
+                write!(f, "set the admin of contract {} to {}", contract, admin)
+            }
+            ProposalAction::BankSend { to_address, amount } => {
+                write!(f, "send {} to {}", readable_coins(amount), to_address)
+            }
+            ProposalAction::ExecuteOnDao { dao, msg, funds } => {
+                write!(f, "execute {} on dao {}", readable_msg(msg), dao)?;
+                if !funds.is_empty() {
+                    write!(f, " with {}", readable_coins(funds))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// Msgs are json encoded, fall back to base64 if they are not valid utf8
+fn readable_msg(msg: &Binary) -> String {
+    String::from_utf8(msg.to_vec()).unwrap_or_else(|_| msg.to_base64())
+}
+
+fn readable_coins(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct AddGrantMsg {
@@ -180,6 +296,20 @@ pub struct ProposalResponse {
     pub concluded_at_height: Option<u64>,
     pub status: ProposalStatus,
     pub msgs: Option<Vec<CosmosMsg>>,
+    pub depends_on: Vec<u64>,
+    // Human readable description of what happens when the proposal passes
+    pub summary: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PauseInfoResponse {
+    pub paused: bool,
+    pub paused_until: Option<u64>,
+    pub guardian: Option<Addr>,
+    pub guardian_cooldown_until: u64,
+    pub pause_votes: Vec<Addr>,
+    pub unpause_votes: Vec<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -3,12 +3,15 @@ use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 
 use crate::error::ContractError;
 // use crate::error::ContractError;
-use crate::msg::{CoreSlot, ExecuteMsg, InstantiateMsg, PeriodInfoResponse, ProposalResponse};
+use crate::msg::{
+    CoreSlot, ExecuteMsg, InstantiateMsg, MigrateMsg, PauseInfoResponse, PeriodInfoResponse,
+    ProposalResponse, WinningGrantsResponse,
+};
 use jmes::msg::GovernanceCoreSlotsResponse as CoreSlotsResponse;
 use jmes::msg::GovernanceQueryMsg as QueryMsg;
 
 use crate::state::VoteOption;
-use crate::{execute, instantiate, query};
+use crate::{execute, instantiate, migrate, query, reply};
 
 #[derive(Debug, Clone)]
 pub struct GovernanceContract(Addr);
@@ -19,7 +22,9 @@ impl GovernanceContract {
     }
 
     pub fn store_code(app: &mut App) -> u64 {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_migrate(migrate)
+            .with_reply(reply);
         app.store_code(Box::new(contract))
     }
 
@@ -36,6 +41,7 @@ impl GovernanceContract {
         period_start_epoch: u64,
         posting_period_length: u64,
         voting_period_length: u64,
+        guardian: Option<String>,
     ) -> StdResult<Self> {
         app.instantiate_contract(
             code_id,
@@ -47,10 +53,11 @@ impl GovernanceContract {
                 period_start_epoch,
                 posting_period_length,
                 voting_period_length,
+                guardian,
             },
             &[],
             label,
-            None,
+            Some(sender.to_string()),
         )
         .map(GovernanceContract)
         .map_err(|err| err.downcast().unwrap())
@@ -150,6 +157,29 @@ impl GovernanceContract {
         // .map(|_| ())
     }
 
+    #[track_caller]
+    pub fn pause(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        app.execute_contract(sender.clone(), self.0.clone(), &ExecuteMsg::Pause {}, &[])
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn unpause(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        app.execute_contract(sender.clone(), self.0.clone(), &ExecuteMsg::Unpause {}, &[])
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn migrate(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        new_code_id: u64,
+    ) -> Result<AppResponse, ContractError> {
+        app.migrate_contract(sender.clone(), self.0.clone(), &MigrateMsg {}, new_code_id)
+            .map_err(|err| err.downcast().unwrap())
+    }
+
     #[track_caller]
     pub fn query_period_info(&self, app: &mut App) -> StdResult<PeriodInfoResponse> {
         app.wrap()
@@ -167,6 +197,18 @@ impl GovernanceContract {
         app.wrap()
             .query_wasm_smart(self.0.clone(), &QueryMsg::CoreSlots {})
     }
+
+    #[track_caller]
+    pub fn query_pause_info(&self, app: &mut App) -> StdResult<PauseInfoResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::PauseInfo {})
+    }
+
+    #[track_caller]
+    pub fn query_winning_grants(&self, app: &mut App) -> StdResult<WinningGrantsResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::WinningGrants {})
+    }
 }
 
 impl From<GovernanceContract> for Addr {
@@ -4,20 +4,32 @@ use std::mem;
 use cosmwasm_std::{
     coins, from_binary,
     testing::{mock_env, MockApi, MockStorage},
-    to_binary, Addr, Coin, CosmosMsg, Timestamp, Uint128, WasmMsg,
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, Storage, Timestamp, Uint128, WasmMsg,
 };
 use cw4::Member;
-use cw_multi_test::{next_block, App, AppBuilder, AppResponse, BankKeeper, Wasm};
+use cw_multi_test::{
+    next_block, App, AppBuilder, AppResponse, BankKeeper, ContractWrapper, Executor, Wasm,
+};
+use cw_storage_plus::{Item, Map};
 use cw_utils::Duration;
 use dao_members::multitest::contract::DaoMembersContract;
 use dao_multisig::{msg::ProposeResponse, multitest::contract::DaoMultisigContract};
 use identityservice::multitest::contract::IdentityserviceContract;
+use jmes::msg::{GovernanceQueryMsg as QueryMsg, SlotVoteResult};
 use serde::__private::de::IdentifierDeserializer;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     error::ContractError,
-    msg::{CoreSlot, ExecuteMsg, ProposalMsg, ProposalPeriod, ProposalResponse},
-    state::{Funding, ProposalStatus, VoteOption},
+    msg::{
+        CoreSlot, ExecuteMsg, InstantiateMsg, ProposalAction, ProposalMsg, ProposalPeriod,
+        ProposalResponse,
+    },
+    state::{
+        Config, CoreSlots, Funding, ProposalStatus, ProposalType, VoteOption, CORE_SLOTS, PAUSE,
+    },
+    upgrades,
 };
 
 use super::contract::GovernanceContract;
@@ -30,6 +42,10 @@ const PERIOD_START_EPOCH: u64 = 0;
 const POSTING_PERIOD_LENGTH: u64 = 40;
 const VOTING_PERIOD_LENGTH: u64 = 40;
 
+const GUARDIAN: &str = "guardian";
+const BRAND_DAO: &str = "brand_dao";
+const CREATIVE_DAO: &str = "creative_dao";
+
 const USER1_FUNDING_COINS: u128 = 1000_000_000;
 const USER1_VOTING_COINS: u128 = 2000_000_000;
 const USER2_VOTING_COINS: u128 = 3000_000_000;
@@ -57,9 +73,19 @@ fn mock_app() -> App {
 }
 
 fn instantiate_contracts(app: &mut App, user1: Addr, user2: Addr, owner: Addr) -> Contracts {
+    let governance_code_id = GovernanceContract::store_code(app);
+    instantiate_contracts_with_code(app, governance_code_id, user1, user2, owner)
+}
+
+fn instantiate_contracts_with_code(
+    app: &mut App,
+    governance_code_id: u64,
+    user1: Addr,
+    user2: Addr,
+    owner: Addr,
+) -> Contracts {
     // Instantiate needed contracts
 
-    let governance_code_id = GovernanceContract::store_code(app);
     println!("governance_code_id {:?}", governance_code_id);
     let governance_contract = GovernanceContract::instantiate(
         app,
@@ -72,6 +98,7 @@ fn instantiate_contracts(app: &mut App, user1: Addr, user2: Addr, owner: Addr) -
         PERIOD_START_EPOCH,
         POSTING_PERIOD_LENGTH,
         VOTING_PERIOD_LENGTH,
+        Some(GUARDIAN.into()),
     )
     .unwrap();
 
@@ -166,6 +193,126 @@ fn instantiate_contracts(app: &mut App, user1: Addr, user2: Addr, owner: Addr) -
     }
 }
 
+// Config layout of 0.1.1, before the guardian
+#[derive(Serialize, Deserialize)]
+struct LegacyConfig {
+    owner: Option<Addr>,
+    art_dealer_addr: Option<Addr>,
+    identityservice_addr: Option<Addr>,
+    proposal_required_deposit: Uint128,
+    proposal_required_percentage: u64,
+    period_start_epoch: u64,
+    posting_period_length: u64,
+    voting_period_length: u64,
+}
+
+// Proposal layout of 0.1.1, before dependencies
+#[derive(Serialize, Deserialize)]
+struct LegacyProposal {
+    id: u64,
+    dao: Addr,
+    title: String,
+    description: String,
+    prop_type: ProposalType,
+    coins_yes: Uint128,
+    coins_no: Uint128,
+    yes_voters: Vec<Addr>,
+    no_voters: Vec<Addr>,
+    deposit_amount: Uint128,
+    start_block: u64,
+    posting_start: u64,
+    voting_start: u64,
+    voting_end: u64,
+    concluded_at_height: Option<u64>,
+    concluded_status: Option<ProposalStatus>,
+    concluded_coins_total: Option<Uint128>,
+    funding: Option<Funding>,
+    msgs: Option<Vec<CosmosMsg>>,
+}
+
+const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+const LEGACY_PROPOSALS: Map<u64, LegacyProposal> = Map::new("proposals");
+
+// Rewrites the state of the current code in the 0.1.1 layout, which had no pause either
+fn downgrade_state(storage: &mut dyn Storage) -> StdResult<()> {
+    let config = LEGACY_CONFIG.load(storage)?;
+    LEGACY_CONFIG.save(storage, &config)?;
+
+    let proposals = LEGACY_PROPOSALS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, proposal) in proposals {
+        LEGACY_PROPOSALS.save(storage, id, &proposal)?;
+    }
+
+    PAUSE.remove(storage);
+    Ok(())
+}
+
+// Runs the current code but leaves the state and version of the last release without migrations
+fn legacy_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let response = crate::instantiate(deps.branch(), env, info, msg)?;
+    downgrade_state(deps.storage)?;
+    cw2::set_contract_version(
+        deps.storage,
+        crate::contract::CONTRACT_NAME,
+        upgrades::v0_2::FROM_VERSION,
+    )?;
+    Ok(response)
+}
+
+fn legacy_execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let response = crate::execute(deps.branch(), env, info, msg)?;
+    downgrade_state(deps.storage)?;
+    Ok(response)
+}
+
+fn store_legacy_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(legacy_execute, legacy_instantiate, crate::query);
+    app.store_code(Box::new(contract))
+}
+
+// Instantiates the current code with the brand and creative core slots held by funded DAOs
+fn core_slots_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let response = crate::instantiate(deps.branch(), env.clone(), info, msg)?;
+    let slot = |dao: &str, proposal_id: u64| SlotVoteResult {
+        dao: Addr::unchecked(dao),
+        yes_ratio: Decimal::percent(60),
+        proposal_voting_end: env.block.height,
+        proposal_funding_end: env.block.height + 1_000_000,
+        proposal_id,
+    };
+    CORE_SLOTS.save(
+        deps.storage,
+        &CoreSlots {
+            brand: Some(slot(BRAND_DAO, 1)),
+            creative: Some(slot(CREATIVE_DAO, 2)),
+            core_tech: None,
+        },
+    )?;
+    Ok(response)
+}
+
+fn store_core_slots_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(crate::execute, core_slots_instantiate, crate::query);
+    app.store_code(Box::new(contract))
+}
+
 fn create_dao(app: &mut App, contracts: Contracts, user1_addr: Addr, user2_addr: Addr) -> Addr {
     create_dao_from(
         app,
@@ -206,6 +353,48 @@ fn create_dao_from(app: &mut App, contracts: Contracts, members: Vec<Member>) ->
     Addr::unchecked(my_dao_addr)
 }
 
+// Posts a governance proposal through the DAO without asserting on the burned deposit,
+// so it can be used several times in one test
+fn dao_propose_helper(
+    app: &mut App,
+    contracts: Contracts,
+    my_dao_addr: Addr,
+    user1: Addr,
+    user2: Addr,
+    proposal_msg: ExecuteMsg,
+) -> Result<AppResponse, dao_multisig::ContractError> {
+    let wasm_msg = WasmMsg::Execute {
+        contract_addr: contracts.governance.addr().into(),
+        msg: to_binary(&proposal_msg).unwrap(),
+        funds: coins(PROPOSAL_REQUIRED_DEPOSIT, "ujmes"),
+    };
+
+    let dao_propose_response = DaoMultisigContract::propose(
+        app,
+        &user1,
+        &my_dao_addr.to_string(),
+        "Dao Proposal".into(),
+        "Wraps Governance Proposal".into(),
+        vec![CosmosMsg::Wasm(wasm_msg)],
+        None,
+        PROPOSAL_REQUIRED_DEPOSIT,
+    )?;
+
+    let proposal_id = from_binary::<ProposeResponse>(&dao_propose_response.data.unwrap())
+        .unwrap()
+        .proposal_id;
+
+    DaoMultisigContract::vote(
+        app,
+        &user2,
+        &my_dao_addr.to_string(),
+        proposal_id,
+        cw3::Vote::Yes,
+    )?;
+
+    DaoMultisigContract::execute(app, &user1, &my_dao_addr.to_string(), proposal_id)
+}
+
 fn gov_vote_helper(
     app: &mut App,
     contracts: Contracts,
@@ -315,6 +504,7 @@ fn text_proposal_no_funding_attached() {
         title: "First Text Proposal".into(),
         description: "Text Proposal Description".into(),
         funding: None,
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -389,6 +579,7 @@ fn text_proposal_with_funding_attached_amount_larger_0() {
             amount: Uint128::from(100_000_000u128),
             duration_in_blocks: 100,
         }),
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -457,6 +648,7 @@ fn text_proposal_with_funding_attached_amount_equal_0() {
             amount: Uint128::zero(),
             duration_in_blocks: 100,
         }),
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -649,6 +841,7 @@ fn core_dao_membership_update_2_members_fails() {
             duration_in_blocks: 3000,
         },
         slot: CoreSlot::Brand {},
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -718,6 +911,9 @@ fn core_dao_membership_update_2_members_fails() {
                     funds: vec![]
                 }
             )]),
+            depends_on: vec![],
+            summary: "Give contract3 the brand core slot, funding 10000000 ujmes for 3000 blocks"
+                .into(),
         }
     );
 
@@ -835,6 +1031,7 @@ fn core_dao_membership_update_1_exceeds_threshold_fails() {
             duration_in_blocks: 3000,
         },
         slot: CoreSlot::Brand {},
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -893,6 +1090,9 @@ fn core_dao_membership_update_1_exceeds_threshold_fails() {
                     funds: vec![]
                 }
             )]),
+            depends_on: vec![],
+            summary: "Give contract3 the brand core slot, funding 10000000 ujmes for 3000 blocks"
+                .into(),
         }
     );
 
@@ -991,6 +1191,7 @@ fn propose_core_slot_brand_with_2_members_fails() {
             duration_in_blocks: 3000,
         },
         slot: CoreSlot::Brand {},
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -1091,6 +1292,7 @@ fn propose_core_slot_brand_with_3_members_2_required_for_threshold_succeeds() {
             duration_in_blocks: 3000,
         },
         slot: CoreSlot::Brand {},
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -1160,6 +1362,9 @@ fn propose_core_slot_brand_with_3_members_2_required_for_threshold_succeeds() {
                     funds: vec![]
                 }
             )]),
+            depends_on: vec![],
+            summary: "Give contract3 the brand core slot, funding 10000000 ujmes for 3000 blocks"
+                .into(),
         }
     )
 }
@@ -1215,6 +1420,7 @@ fn propose_core_slot_brand_with_3_members_1_exceeds_threshold_fails() {
             duration_in_blocks: 3000,
         },
         slot: CoreSlot::Brand {},
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -1323,6 +1529,7 @@ fn propose_core_slot_update_membership_before_conclude_1_exceeds_threshold_set_c
             duration_in_blocks: 3000,
         },
         slot: CoreSlot::Brand {},
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -1474,6 +1681,7 @@ fn propose_core_slot_update_membership_before_conclude_2_members_set_core_slot_f
             duration_in_blocks: 3000,
         },
         slot: CoreSlot::Brand {},
+        depends_on: vec![],
     });
 
     // Create, vote on and execute the dao proposal
@@ -2657,3 +2865,802 @@ fn propose_core_slot_update_membership_before_conclude_2_members_set_core_slot_f
 //         vec![]
 //     );
 // }
+
+#[test]
+fn migrate_from_0_1_1_keeps_open_proposals() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let legacy_code_id = store_legacy_code(&mut app);
+    let contracts = instantiate_contracts_with_code(
+        &mut app,
+        legacy_code_id,
+        user1.clone(),
+        user2.clone(),
+        owner.clone(),
+    );
+
+    // Register a DAO and post a proposal on the legacy code
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "First Text Proposal".into(),
+        description: "Text Proposal Description".into(),
+        funding: None,
+        depends_on: vec![],
+    });
+
+    DaoMultisigContract::gov_proposal_helper(
+        &mut app,
+        my_dao_addr,
+        &contracts.governance.addr().clone(),
+        user1.clone(),
+        user2.clone(),
+        to_binary(&proposal_msg).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT,
+    )
+    .unwrap();
+
+    let version = cw2::query_contract_info(&app.wrap(), contracts.governance.addr()).unwrap();
+    assert_eq!(version.version, upgrades::v0_2::FROM_VERSION);
+
+    // The state is stored in the 0.1.1 layout
+    let raw_config = app
+        .wrap()
+        .query_wasm_raw(contracts.governance.addr(), b"config".as_slice())
+        .unwrap()
+        .unwrap();
+    assert!(!String::from_utf8(raw_config).unwrap().contains("guardian"));
+    let raw_proposal = app
+        .wrap()
+        .query_wasm_raw(
+            contracts.governance.addr(),
+            LEGACY_PROPOSALS.key(1).to_vec(),
+        )
+        .unwrap()
+        .unwrap();
+    let raw_proposal = String::from_utf8(raw_proposal).unwrap();
+    assert!(raw_proposal.contains("First Text Proposal"));
+    assert!(!raw_proposal.contains("depends_on"));
+    assert!(app
+        .wrap()
+        .query_wasm_raw(contracts.governance.addr(), b"pause".as_slice())
+        .unwrap()
+        .is_none());
+
+    // Only the admin can migrate the contract
+    let new_code_id = GovernanceContract::store_code(&mut app);
+    app.migrate_contract(
+        user2.clone(),
+        contracts.governance.addr().clone(),
+        &crate::msg::MigrateMsg {},
+        new_code_id,
+    )
+    .unwrap_err();
+
+    contracts
+        .governance
+        .migrate(&mut app, &user1, new_code_id)
+        .unwrap();
+
+    let version = cw2::query_contract_info(&app.wrap(), contracts.governance.addr()).unwrap();
+    assert_eq!(version.contract, "governance");
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+    // The legacy config and proposal are read back without a guardian and dependencies
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(contracts.governance.addr(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config.identityservice_addr,
+        Some(contracts.identityservice.addr().clone())
+    );
+    assert_eq!(config.guardian, None);
+    assert_eq!(config.guardian_cooldown, None);
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert_eq!(proposal.depends_on, Vec::<u64>::new());
+
+    // Governance is not paused
+    let pause_info = contracts.governance.query_pause_info(&mut app).unwrap();
+    assert!(!pause_info.paused);
+    assert_eq!(pause_info.guardian, None);
+    assert!(pause_info.pause_votes.is_empty());
+
+    // The proposal posted before the migration can still be voted on and concluded
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1.clone(),
+        VoteOption::Yes,
+        user2.clone(),
+        VoteOption::No,
+        1,
+    )
+    .unwrap();
+
+    let final_proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert_eq!(final_proposal.status, ProposalStatus::SuccessConcluded);
+}
+
+#[test]
+fn migrate_to_same_version_is_a_noop() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2, owner);
+
+    let new_code_id = GovernanceContract::store_code(&mut app);
+    contracts
+        .governance
+        .migrate(&mut app, &user1, new_code_id)
+        .unwrap();
+
+    let version = cw2::query_contract_info(&app.wrap(), contracts.governance.addr()).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn proposal_depending_on_failed_proposal_is_not_executed() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Proposal 1 approves a feature, proposal 2 requests funding if the feature gets approved
+    let feature_proposal = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "Feature Proposal".into(),
+        description: "Feature Proposal Description".into(),
+        funding: None,
+        depends_on: vec![],
+    });
+    dao_propose_helper(
+        &mut app,
+        contracts.clone(),
+        my_dao_addr.clone(),
+        user1.clone(),
+        user2.clone(),
+        feature_proposal,
+    )
+    .unwrap();
+
+    let funding_proposal = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "Funding Proposal".into(),
+        description: "Funding Proposal Description".into(),
+        funding: Some(Funding {
+            amount: Uint128::from(100_000_000u128),
+            duration_in_blocks: 100,
+        }),
+        depends_on: vec![1],
+    });
+    dao_propose_helper(
+        &mut app,
+        contracts.clone(),
+        my_dao_addr.clone(),
+        user1.clone(),
+        user2.clone(),
+        funding_proposal,
+    )
+    .unwrap();
+
+    let proposal = contracts.governance.query_proposal(&mut app, 2).unwrap();
+    assert_eq!(proposal.depends_on, vec![1]);
+
+    // A proposal can only depend on existing proposals
+    let unknown_dependency = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "Unknown Dependency".into(),
+        description: "Depends on a proposal that does not exist".into(),
+        funding: None,
+        depends_on: vec![42],
+    });
+    dao_propose_helper(
+        &mut app,
+        contracts.clone(),
+        my_dao_addr.clone(),
+        user1.clone(),
+        user2.clone(),
+        unknown_dependency,
+    )
+    .unwrap_err();
+
+    // Skip period from Posting to Voting
+    let period_info = contracts.governance.query_period_info(&mut app).unwrap();
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(period_info.posting_period_length);
+        block.height += period_info.posting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    // The feature gets rejected while the funding passes
+    contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::No)
+        .unwrap();
+    contracts
+        .governance
+        .vote(&mut app, &user1, 2, VoteOption::Yes)
+        .unwrap();
+
+    // Skip period from Voting to Posting so we can conclude the proposals
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(period_info.voting_period_length);
+        block.height += period_info.voting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    // The dependency has to be concluded first
+    let err = contracts
+        .governance
+        .conclude(&mut app, &user1, 2)
+        .unwrap_err();
+    assert_eq!(err, ContractError::DependencyNotConcluded { id: 1 });
+
+    contracts.governance.conclude(&mut app, &user1, 1).unwrap();
+    let response = contracts.governance.conclude(&mut app, &user1, 2).unwrap();
+    assert!(response.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "dependencies_met" && attr.value == "false")));
+
+    // The funding proposal passed but no grant was registered for it
+    let proposal = contracts.governance.query_proposal(&mut app, 2).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::DependencyFailedConcluded);
+    let winning_grants = contracts.governance.query_winning_grants(&mut app).unwrap();
+    assert_eq!(winning_grants.winning_grants, vec![]);
+
+    // Proposals can't depend on a proposal that already failed
+    let failed_dependency = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "Failed Dependency".into(),
+        description: "Depends on a rejected proposal".into(),
+        funding: None,
+        depends_on: vec![1],
+    });
+    dao_propose_helper(
+        &mut app,
+        contracts.clone(),
+        my_dao_addr,
+        user1,
+        user2,
+        failed_dependency,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn failed_dependency_fails_the_whole_chain() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner.clone());
+
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // Proposal 1 <- proposal 2 <- proposal 3, only the last one requests funding
+    for (title, funding, depends_on) in [
+        ("A", None, vec![]),
+        ("B", None, vec![1]),
+        (
+            "C",
+            Some(Funding {
+                amount: Uint128::from(100_000_000u128),
+                duration_in_blocks: 100,
+            }),
+            vec![2],
+        ),
+    ] {
+        let proposal_msg = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+            title: title.into(),
+            description: format!("Proposal {}", title),
+            funding,
+            depends_on,
+        });
+        dao_propose_helper(
+            &mut app,
+            contracts.clone(),
+            my_dao_addr.clone(),
+            user1.clone(),
+            user2.clone(),
+            proposal_msg,
+        )
+        .unwrap();
+    }
+
+    // Skip period from Posting to Voting
+    let period_info = contracts.governance.query_period_info(&mut app).unwrap();
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(period_info.posting_period_length);
+        block.height += period_info.posting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    // A gets rejected while B and C pass
+    contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::No)
+        .unwrap();
+    contracts
+        .governance
+        .vote(&mut app, &user1, 2, VoteOption::Yes)
+        .unwrap();
+    contracts
+        .governance
+        .vote(&mut app, &user1, 3, VoteOption::Yes)
+        .unwrap();
+
+    // Skip period from Voting to Posting so we can conclude the proposals
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(period_info.voting_period_length);
+        block.height += period_info.voting_period_length / SECONDS_PER_BLOCK;
+    });
+
+    for id in 1..=3 {
+        contracts.governance.conclude(&mut app, &user1, id).unwrap();
+    }
+
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::ExpiredConcluded);
+    let proposal = contracts.governance.query_proposal(&mut app, 2).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::DependencyFailedConcluded);
+
+    // C depends on a proposal that passed, but that proposal did not take effect either
+    let proposal = contracts.governance.query_proposal(&mut app, 3).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::DependencyFailedConcluded);
+    let winning_grants = contracts.governance.query_winning_grants(&mut app).unwrap();
+    assert_eq!(winning_grants.winning_grants, vec![]);
+
+    // New proposals can't depend on B anymore
+    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "D".into(),
+        description: "Proposal D".into(),
+        funding: None,
+        depends_on: vec![2],
+    });
+    dao_propose_helper(
+        &mut app,
+        contracts.clone(),
+        my_dao_addr,
+        user1,
+        user2,
+        proposal_msg,
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn action_proposal_sends_funds_from_treasury() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let member1 = Addr::unchecked("member1");
+    let member2 = Addr::unchecked("member2");
+    let recipient = Addr::unchecked("recipient");
+
+    let contracts = instantiate_contracts(&mut app, member1.clone(), member2.clone(), owner);
+
+    let my_dao_addr = create_dao_from(
+        &mut app,
+        contracts.clone(),
+        vec![
+            Member {
+                addr: member1.to_string(),
+                weight: 26,
+            },
+            Member {
+                addr: member2.to_string(),
+                weight: 26,
+            },
+            Member {
+                addr: "member3".into(),
+                weight: 10,
+            },
+        ],
+    );
+
+    // Actions can only be proposed by the CoreTech DAO
+    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::CoreSlot {
+        title: "Make me CoreTech".into(),
+        description: "Serving the chain".into(),
+        funding: Funding {
+            amount: 10_000_000u128.into(),
+            duration_in_blocks: 3000,
+        },
+        slot: CoreSlot::CoreTech {},
+        depends_on: vec![],
+    });
+
+    DaoMultisigContract::gov_proposal_helper(
+        &mut app,
+        my_dao_addr.clone(),
+        &contracts.governance.addr().clone(),
+        member1.clone(),
+        member2.clone(),
+        to_binary(&proposal_msg).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT,
+    )
+    .unwrap();
+
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        member1.clone(),
+        VoteOption::Yes,
+        member2.clone(),
+        VoteOption::No,
+        1,
+    )
+    .unwrap();
+
+    let core_slots = contracts.governance.query_core_slots(&mut app).unwrap();
+    assert_eq!(core_slots.core_tech.unwrap().dao, my_dao_addr);
+
+    // Fund the governance treasury
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, contracts.governance.addr(), coins(1000, "ujmes"))
+            .unwrap();
+    });
+
+    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::Action {
+        title: "Pay the recipient".into(),
+        description: "Send funds from the treasury".into(),
+        actions: vec![ProposalAction::BankSend {
+            to_address: recipient.to_string(),
+            amount: coins(1000, "ujmes"),
+        }],
+        depends_on: vec![],
+    });
+
+    dao_propose_helper(
+        &mut app,
+        contracts.clone(),
+        my_dao_addr.clone(),
+        member1.clone(),
+        member2.clone(),
+        proposal_msg,
+    )
+    .unwrap();
+
+    let proposal = contracts.governance.query_proposal(&mut app, 2).unwrap();
+    assert_eq!(proposal.summary, "Actions: send 1000ujmes to recipient");
+    assert_eq!(
+        proposal.msgs,
+        Some(vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(1000, "ujmes"),
+        })])
+    );
+
+    gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        member1.clone(),
+        VoteOption::Yes,
+        member2,
+        VoteOption::No,
+        2,
+    )
+    .unwrap();
+
+    assert_eq!(
+        app.wrap().query_all_balances(recipient).unwrap(),
+        coins(1000, "ujmes")
+    );
+}
+
+#[test]
+fn guardian_pause_blocks_governance_until_deadline() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let guardian = Addr::unchecked(GUARDIAN);
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2, owner);
+
+    // Only the guardian or core slot DAOs can pause
+    let err = contracts.governance.pause(&mut app, &user1).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    contracts.governance.pause(&mut app, &guardian).unwrap();
+
+    let pause_info = contracts.governance.query_pause_info(&mut app).unwrap();
+    assert!(pause_info.paused);
+    assert_eq!(
+        pause_info.paused_until,
+        Some(app.block_info().time.seconds() + 604_800)
+    );
+
+    // Votes and conclusions are rejected while paused
+    let err = contracts
+        .governance
+        .vote(&mut app, &user1, 1, VoteOption::Yes)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+    let err = contracts
+        .governance
+        .conclude(&mut app, &user1, 1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    let err = contracts.governance.pause(&mut app, &guardian).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    contracts.governance.unpause(&mut app, &guardian).unwrap();
+    assert!(
+        !contracts
+            .governance
+            .query_pause_info(&mut app)
+            .unwrap()
+            .paused
+    );
+
+    // The guardian can't chain pauses to freeze governance
+    let err = contracts.governance.pause(&mut app, &guardian).unwrap_err();
+    assert!(matches!(err, ContractError::GuardianCooldown { .. }));
+
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(2 * 604_800);
+        block.height += 2 * 604_800 / SECONDS_PER_BLOCK;
+    });
+    contracts.governance.pause(&mut app, &guardian).unwrap();
+
+    // The pause ends automatically after the deadline
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(604_800);
+        block.height += 604_800 / SECONDS_PER_BLOCK;
+    });
+    assert!(
+        !contracts
+            .governance
+            .query_pause_info(&mut app)
+            .unwrap()
+            .paused
+    );
+    let err = contracts
+        .governance
+        .unpause(&mut app, &guardian)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotPaused {});
+}
+
+#[test]
+fn guardian_cooldown_starts_when_pause_ends() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let guardian = Addr::unchecked(GUARDIAN);
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2, owner);
+    let governance = contracts.governance.addr().clone();
+
+    contracts.governance.pause(&mut app, &guardian).unwrap();
+
+    // Lifting the pause early starts the cooldown right away
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(259_200);
+        block.height += 259_200 / SECONDS_PER_BLOCK;
+    });
+    contracts.governance.unpause(&mut app, &guardian).unwrap();
+    let pause_info = contracts.governance.query_pause_info(&mut app).unwrap();
+    assert_eq!(
+        pause_info.guardian_cooldown_until,
+        app.block_info().time.seconds() + 604_800
+    );
+
+    // Only governance itself can change the cooldown
+    let set_cooldown_msg = ExecuteMsg::SetGuardianCooldown { seconds: 86_400 };
+    let err = app
+        .execute_contract(user1, governance.clone(), &set_cooldown_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    app.execute_contract(governance.clone(), governance, &set_cooldown_msg, &[])
+        .unwrap();
+
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(604_800);
+        block.height += 604_800 / SECONDS_PER_BLOCK;
+    });
+    contracts.governance.pause(&mut app, &guardian).unwrap();
+
+    // A pause that runs out counts the cooldown from its deadline
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(604_800);
+        block.height += 604_800 / SECONDS_PER_BLOCK;
+    });
+    let err = contracts.governance.pause(&mut app, &guardian).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::GuardianCooldown {
+            until: app.block_info().time.seconds() + 86_400
+        }
+    );
+
+    app.update_block(|mut block| {
+        block.time = block.time.plus_seconds(86_400);
+        block.height += 86_400 / SECONDS_PER_BLOCK;
+    });
+    contracts.governance.pause(&mut app, &guardian).unwrap();
+}
+
+#[test]
+fn guardian_cannot_lift_core_slot_pause() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let guardian = Addr::unchecked(GUARDIAN);
+    let brand_dao = Addr::unchecked(BRAND_DAO);
+    let creative_dao = Addr::unchecked(CREATIVE_DAO);
+
+    let code_id = store_core_slots_code(&mut app);
+    let contracts = instantiate_contracts_with_code(&mut app, code_id, user1, user2, owner);
+
+    // Core slot DAOs pause governance once they reach the quorum
+    contracts.governance.pause(&mut app, &brand_dao).unwrap();
+    assert!(
+        !contracts
+            .governance
+            .query_pause_info(&mut app)
+            .unwrap()
+            .paused
+    );
+    contracts.governance.pause(&mut app, &creative_dao).unwrap();
+    assert!(
+        contracts
+            .governance
+            .query_pause_info(&mut app)
+            .unwrap()
+            .paused
+    );
+
+    // The guardian can't override them
+    let err = contracts
+        .governance
+        .unpause(&mut app, &guardian)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Lifting the pause takes a quorum as well
+    contracts.governance.unpause(&mut app, &brand_dao).unwrap();
+    let pause_info = contracts.governance.query_pause_info(&mut app).unwrap();
+    assert!(pause_info.paused);
+    assert_eq!(pause_info.unpause_votes, vec![brand_dao]);
+
+    contracts
+        .governance
+        .unpause(&mut app, &creative_dao)
+        .unwrap();
+    assert!(
+        !contracts
+            .governance
+            .query_pause_info(&mut app)
+            .unwrap()
+            .paused
+    );
+
+    // A pause of the guardian can still be lifted by it alone
+    contracts.governance.pause(&mut app, &guardian).unwrap();
+    contracts.governance.unpause(&mut app, &guardian).unwrap();
+    assert!(
+        !contracts
+            .governance
+            .query_pause_info(&mut app)
+            .unwrap()
+            .paused
+    );
+}
+
+#[test]
+fn dissolved_dao_cannot_propose() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner);
+    let my_dao = create_dao(&mut app, contracts.clone(), user1, user2);
+
+    contracts
+        .identityservice
+        .dissolve_dao(&mut app, &my_dao)
+        .unwrap();
+
+    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "First Text Proposal".into(),
+        description: "Text Proposal Description".into(),
+        funding: None,
+        depends_on: vec![],
+    });
+    let err = app
+        .execute_contract(
+            my_dao,
+            contracts.governance.addr().clone(),
+            &proposal_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::DaoDissolved {}
+    );
+}
+
+#[test]
+fn failed_dao_fee_refund_does_not_block_conclude() {
+    let mut app = mock_app();
+
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let contracts = instantiate_contracts(&mut app, user1.clone(), user2.clone(), owner);
+    let my_dao_addr = create_dao(&mut app, contracts.clone(), user1.clone(), user2.clone());
+
+    // The identityservice no longer accepts refunds from this governance contract
+    contracts
+        .identityservice
+        .update_config(
+            &mut app,
+            contracts.governance.addr(),
+            None,
+            Some("other_governance".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let proposal_msg = ExecuteMsg::Propose(ProposalMsg::TextProposal {
+        title: "First Text Proposal".into(),
+        description: "Text Proposal Description".into(),
+        funding: None,
+        depends_on: vec![],
+    });
+    DaoMultisigContract::gov_proposal_helper(
+        &mut app,
+        my_dao_addr,
+        &contracts.governance.addr().clone(),
+        user1.clone(),
+        user2.clone(),
+        to_binary(&proposal_msg).unwrap(),
+        PROPOSAL_REQUIRED_DEPOSIT,
+    )
+    .unwrap();
+
+    let response = gov_vote_helper(
+        &mut app,
+        contracts.clone(),
+        user1,
+        VoteOption::Yes,
+        user2,
+        VoteOption::No,
+        1,
+    )
+    .unwrap();
+    assert!(response.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "refund_dao_fee_failed")));
+
+    let proposal = contracts.governance.query_proposal(&mut app, 1).unwrap();
+    assert_eq!(proposal.status, ProposalStatus::SuccessConcluded);
+}
@@ -1,6 +1,6 @@
 use crate::{
     error::ContractError,
-    msg::{CoreSlot, Feature},
+    msg::{CoreSlot, Feature, ProposalAction},
 };
 use cosmwasm_std::{Addr, CosmosMsg, Decimal, Env, QuerierWrapper, StdResult, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
@@ -23,6 +23,8 @@ pub const CORE_SLOTS: Item<CoreSlots> = Item::new("core_slots");
 
 pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 
+pub const PAUSE: Item<PauseInfo> = Item::new("pause");
+
 pub struct ProposalIndexes<'a> {
     // pk goes to second tuple element
     pub status: MultiIndex<'a, String, Proposal, u64>,
@@ -42,6 +44,9 @@ pub fn proposals<'a>() -> IndexedMap<'a, u64, Proposal, ProposalIndexes<'a>> {
                 Some(status) => match status {
                     ProposalStatus::SuccessConcluded => "success_concluded".to_string(),
                     ProposalStatus::ExpiredConcluded => "expired_concluded".to_string(),
+                    ProposalStatus::DependencyFailedConcluded => {
+                        "dependency_failed_concluded".to_string()
+                    }
                     _ => "active".to_string(),
                 },
                 None => "active".to_string(),
@@ -89,7 +94,37 @@ pub struct Config {
     pub posting_period_length: u64,
     // Length in seconds of the posting period, e.g.  606864 for ~ 1 Week (year/52)
     pub voting_period_length: u64,
+    // Address that can pause governance in an emergency, configs stored before 0.2.0 have none
+    #[serde(default)]
+    pub guardian: Option<Addr>,
+    // Seconds the guardian has to wait after a pause ended before pausing again,
+    // the default is used until it is set through a proposal
+    #[serde(default)]
+    pub guardian_cooldown: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct PauseInfo {
+    // Epoch in seconds when the pause ends automatically
+    pub paused_until: Option<u64>,
+    // Core slot DAOs that voted to pause or unpause governance
+    pub pause_votes: Vec<Addr>,
+    pub unpause_votes: Vec<Addr>,
+    // The guardian can't pause again before this epoch
+    pub guardian_cooldown_until: u64,
+    // Whether the current pause was started by the guardian
+    #[serde(default)]
+    pub guardian_paused: bool,
 }
+
+impl PauseInfo {
+    pub fn is_paused(&self, env: &Env) -> bool {
+        self.paused_until
+            .map_or(false, |until| env.block.time.seconds() < until)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct Proposal {
@@ -112,6 +147,9 @@ pub struct Proposal {
     pub concluded_coins_total: Option<Uint128>,
     pub funding: Option<Funding>,
     pub msgs: Option<Vec<CosmosMsg>>,
+    // Proposals stored before 0.2.0 have no dependencies
+    #[serde(default)]
+    pub depends_on: Vec<u64>,
 }
 
 impl Proposal {
@@ -205,6 +243,40 @@ impl Proposal {
         }
     }
 
+    // Describes what happens when the proposal passes so voters don't have to decode msgs
+    pub fn summary(&self) -> String {
+        let mut summary = match &self.prop_type {
+            ProposalType::Text {} => "Text proposal".to_string(),
+            ProposalType::FeatureRequest(Feature::ArtDealer { approved }) => {
+                format!("Approve {} to mint {} art nfts", self.dao, approved)
+            }
+            ProposalType::Improvement {} => format!(
+                "Execute {} raw msgs",
+                self.msgs
+                    .as_ref()
+                    .map(|msgs| msgs.len())
+                    .unwrap_or_default()
+            ),
+            ProposalType::Action(actions) => {
+                let actions: Vec<String> = actions.iter().map(|a| a.to_string()).collect();
+                format!("Actions: {}", actions.join("; "))
+            }
+            ProposalType::CoreSlot(slot) => format!("Give {} the {} core slot", self.dao, slot),
+            ProposalType::RevokeProposal(id) => {
+                format!("Revoke the core slot granted by proposal {}", id)
+            }
+        };
+
+        if let Some(funding) = &self.funding {
+            summary.push_str(&format!(
+                ", funding {} ujmes for {} blocks",
+                funding.amount, funding.duration_in_blocks
+            ));
+        }
+
+        summary
+    }
+
     pub fn validate(&self) -> Result<(), ContractError> {
         // Title validation
         if self.title.len() < MIN_TITLE_LENGTH {
@@ -257,6 +329,8 @@ pub enum ProposalStatus {
     Expired,
     SuccessConcluded,
     ExpiredConcluded,
+    // Passed, but a proposal it depends on did not
+    DependencyFailedConcluded,
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -264,6 +338,7 @@ pub enum ProposalType {
     Text {},
     FeatureRequest(Feature),
     Improvement {},
+    Action(Vec<ProposalAction>),
     CoreSlot(CoreSlot),
     RevokeProposal(u64),
 }
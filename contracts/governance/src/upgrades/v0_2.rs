@@ -0,0 +1,20 @@
+use cosmwasm_std::DepsMut;
+
+use crate::contract::CONTRACT_NAME;
+use crate::error::ContractError;
+
+// Last release before the migrate entry point did anything
+pub const FROM_VERSION: &str = "0.1.1";
+pub const TO_VERSION: &str = "0.2.0";
+
+pub fn migrate(deps: DepsMut) -> Result<(), ContractError> {
+    // make sure the contract is being upgraded from the release this module expects
+    cw2::assert_contract_version(deps.storage, CONTRACT_NAME, FROM_VERSION)?;
+
+    // The storage layout of 0.1.1 is still valid for 0.2.0, proposals stored without
+    // `depends_on` are read back with an empty dependency list
+
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, TO_VERSION)?;
+
+    Ok(())
+}
@@ -1,32 +1,54 @@
 // #[cfg(not(feature = "library"))]
 use cosmwasm_std::{entry_point, Order};
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError,
-    StdResult, SubMsg, WasmMsg,
+    to_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env, Event, MessageInfo, Reply,
+    ReplyOn, Response, StdError, StdResult, Storage, SubMsg, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw4::MemberChangedHookMsg;
 use cw_storage_plus::Bound;
-use cw_utils::parse_reply_instantiate_data;
+use cw_utils::{ensure_from_older_version, must_pay, nonpayable, parse_reply_instantiate_data};
 use dao_multisig::msg::InstantiateResponse;
 use dao_multisig::state::Executor;
 
 use crate::error::ContractError;
 use crate::msg::{
-    DaosResponse, ExecuteMsg, GetIdentityByNameResponse, GetIdentityByOwnerResponse,
-    InstantiateMsg, Ordering, QueryMsg, RegisterDaoMsg,
+    AttestationIssuersResponse, AttestationResponse, AttestationsResponse, CanonicalNameResponse,
+    DaoFeeResponse, DaoTemplateResponse, DaoTemplatesResponse, DaosOfMemberResponse, DaosResponse,
+    ExecuteMsg, GetIdentityByNameResponse, GetIdentityByOwnerResponse, IdentitiesByOwnersResponse,
+    IdentitiesResponse, InstantiateMsg, MigrateMsg, Ordering, OwnerIdentity,
+    PendingIdentityTransferResponse, QueryMsg, RegisterDaoMsg, UpdateConfigMsg,
 };
-use crate::state::{identities, next_dao_id, Config, IdType, Identity, CONFIG, DAOS};
+use crate::state::{
+    attestations, canonical_name, identities, next_dao_id, Attestation, Config, DaoFee,
+    DaoTemplate, IdType, Identity, IdentityStatus, PendingDao, Profile, ATTESTATION_ISSUERS,
+    CONFIG, DAOS, DAO_FEES, DAO_IDS, DAO_MEMBERSHIPS, DAO_MEMBERS_CONTRACTS, DAO_TEMPLATES,
+    DAO_TEMPLATE_VERSIONS, LAST_DAO_REGISTRATION, PENDING_DAO, PENDING_TRANSFERS,
+};
+use crate::upgrades;
 
 const MIN_NAME_LENGTH: u64 = 3;
 const MAX_NAME_LENGTH: u64 = 20;
 
+// profile size limits
+const MAX_DISPLAY_NAME_LENGTH: u64 = 64;
+const MAX_URI_LENGTH: u64 = 256;
+const MAX_BIO_LENGTH: u64 = 512;
+const MAX_SOCIALS: u64 = 8;
+const MAX_SOCIAL_PLATFORM_LENGTH: u64 = 32;
+const MAX_SOCIAL_HANDLE_LENGTH: u64 = 64;
+
+// attestation size limits
+const MAX_ATTESTATION_KIND_LENGTH: u64 = 64;
+const MAX_DATA_HASH_LENGTH: u64 = 64;
+
 // settings for pagination
 const MAX_LIMIT: u32 = 30;
 const DEFAULT_LIMIT: u32 = 10;
 
 // version info for migration info
-const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const INSTANTIATE_DAO_MEMBERS_REPLY_ID: u64 = 1u64;
 const INSTANTIATE_DAO_MULTISIG_REPLY_ID: u64 = 2u64;
@@ -34,7 +56,7 @@ const INSTANTIATE_DAO_MULTISIG_REPLY_ID: u64 = 2u64;
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -45,14 +67,54 @@ pub fn instantiate(
         dao_members_code_id: msg.dao_members_code_id,
         dao_multisig_code_id: msg.dao_multisig_code_id,
         governance_addr: msg.governance_addr,
+        name_fees: msg.name_fees,
+        name_registration_period: msg.name_registration_period,
+        name_grace_period: msg.name_grace_period,
+        dao_fee: msg.dao_fee,
+        dao_fee_recipient: msg.dao_fee_recipient,
+        dao_fee_refund_period: msg.dao_fee_refund_period,
+        dao_registration_cooldown: msg.dao_registration_cooldown,
+        dao_template_version: 1,
     };
 
     CONFIG.save(deps.storage, &config)?;
+    DAO_TEMPLATES.save(
+        deps.storage,
+        config.dao_template_version,
+        &DaoTemplate {
+            version: config.dao_template_version,
+            dao_members_code_id: config.dao_members_code_id,
+            dao_multisig_code_id: config.dao_multisig_code_id,
+            added_at: env.block.time.seconds(),
+        },
+    )?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("owner", info.sender))
 }
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version = get_contract_version(deps.storage)?.version;
+
+    // Upgrade the state release by release, every upgrade asserts the version it starts from
+    // and bumps the stored version to the release it migrates to
+    if get_contract_version(deps.storage)?.version == upgrades::v0_2::FROM_VERSION {
+        upgrades::v0_2::migrate(deps.branch())?;
+    }
+    if get_contract_version(deps.storage)?.version == upgrades::v0_3::FROM_VERSION {
+        upgrades::v0_3::migrate(deps.branch(), &env)?;
+    }
+
+    // Make sure the correct contract is being upgraded and that it's not a downgrade
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored_version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -65,59 +127,339 @@ pub fn execute(
         ExecuteMsg::RegisterDao(register_dao_msg) => {
             execute_register_dao(deps, env, info, register_dao_msg)
         }
+        ExecuteMsg::TransferIdentity { to } => execute_transfer_identity(deps, env, info, to),
+        ExecuteMsg::AcceptIdentityTransfer { from } => {
+            execute_accept_identity_transfer(deps, env, info, from)
+        }
+        ExecuteMsg::CancelIdentityTransfer {} => execute_cancel_identity_transfer(deps, env, info),
+        ExecuteMsg::UpdateProfile { profile } => execute_update_profile(deps, env, info, profile),
+        ExecuteMsg::RenewName {} => execute_renew_name(deps, env, info),
+        ExecuteMsg::DissolveDao {} => execute_dissolve_dao(deps, env, info),
+        ExecuteMsg::RefundDaoFee { dao } => execute_refund_dao_fee(deps, env, info, dao),
+        ExecuteMsg::SweepDaoFee { dao } => execute_sweep_dao_fee(deps, env, info, dao),
+        ExecuteMsg::MemberChangedHook(hook_msg) => {
+            execute_member_changed_hook(deps, env, info, hook_msg)
+        }
+        ExecuteMsg::UpdateConfig(update_config_msg) => {
+            execute_update_config(deps, env, info, update_config_msg)
+        }
+        ExecuteMsg::Attest {
+            subject,
+            kind,
+            expires,
+            data_hash,
+        } => execute_attest(deps, env, info, subject, kind, expires, data_hash),
+        ExecuteMsg::Revoke { subject, kind } => execute_revoke(deps, env, info, subject, kind),
+        ExecuteMsg::UpdateAttestationIssuers { add, remove } => {
+            execute_update_attestation_issuers(deps, env, info, add, remove)
+        }
     }
 }
 
 pub fn execute_register_user(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     name: String,
 ) -> Result<Response, ContractError> {
     // Validate requested identity name
     validate_name(&name)?;
 
+    let config = CONFIG.load(deps.storage)?;
+
     // Check if requesting address already has an identity registered
-    let maybe_address_exists = identities().may_load(deps.storage, info.sender.to_string());
+    let maybe_address_exists =
+        load_active_identity_by_owner(deps.storage, &config, &env, &info.sender);
 
     if maybe_address_exists?.is_some() {
         return Err(ContractError::AlreadyRegistered {});
     }
 
     // Check if requested name is already taken
-    let maybe_name_exists = identities().idx.name.item(deps.storage, name.clone());
+    let maybe_name_exists = load_active_identity_by_name(deps.storage, &config, &env, &name);
 
     if maybe_name_exists?.is_some() {
         return Err(ContractError::NameTaken { name });
     }
 
+    let fee_msgs = collect_name_fee(&config, &info, &name)?;
+
     // Store requested name as an identity struct
     let identity = Identity {
         owner: info.sender,
         name,
         id_type: IdType::User,
+        profile: None,
+        expires: config
+            .name_registration_period
+            .map(|period| env.block.time.seconds() + period),
+        status: IdentityStatus::Active,
     };
 
     identities().save(deps.storage, identity.owner.to_string(), &identity)?;
 
-    Ok(Response::default())
+    Ok(Response::default().add_messages(fee_msgs))
 }
 
-pub fn execute_register_dao(
+pub fn execute_renew_name(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut identity = load_active_identity_by_owner(deps.storage, &config, &env, &info.sender)?
+        .ok_or(ContractError::NoIdentity {})?;
+
+    let (period, expires) = match (config.name_registration_period, identity.expires) {
+        (Some(period), Some(expires)) => (period, expires),
+        _ => {
+            return Err(ContractError::NameDoesNotExpire {
+                name: identity.name,
+            })
+        }
+    };
+
+    let fee_msgs = collect_name_fee(&config, &info, &identity.name)?;
+
+    // Renewing during the grace period continues from now, so the lapsed time is not paid for
+    identity.expires = Some(expires.max(env.block.time.seconds()) + period);
+    identities().save(deps.storage, identity.owner.to_string(), &identity)?;
+
+    Ok(Response::new()
+        .add_messages(fee_msgs)
+        .add_attribute("action", "renew_name")
+        .add_attribute("name", identity.name)
+        .add_attribute("expires", identity.expires.unwrap_or_default().to_string()))
+}
+
+// Checks the fee for the name was paid and forwards it to the governance contract
+fn collect_name_fee(
+    config: &Config,
+    info: &MessageInfo,
+    name: &str,
+) -> Result<Vec<BankMsg>, ContractError> {
+    let paid = must_pay_fee(info, config.name_fee(name))?;
+
+    Ok(paid
+        .into_iter()
+        .map(|paid| BankMsg::Send {
+            to_address: config.governance_addr.to_string(),
+            amount: vec![paid],
+        })
+        .collect())
+}
+
+// Checks at least the fee was paid and returns the paid amount, no funds are accepted without a fee
+fn must_pay_fee(info: &MessageInfo, fee: Option<Coin>) -> Result<Option<Coin>, ContractError> {
+    let fee = match fee {
+        Some(fee) => fee,
+        None => {
+            nonpayable(info)?;
+            return Ok(None);
+        }
+    };
+
+    let paid = must_pay(info, &fee.denom)?;
+    if paid < fee.amount {
+        return Err(ContractError::InsufficientFee {
+            required: fee.to_string(),
+        });
+    }
+
+    Ok(Some(Coin {
+        denom: fee.denom,
+        amount: paid,
+    }))
+}
+
+// Loads the identity of the owner, released identities are removed so the owner can register again
+fn load_active_identity_by_owner(
+    storage: &mut dyn Storage,
+    config: &Config,
+    env: &Env,
+    owner: &Addr,
+) -> StdResult<Option<Identity>> {
+    let maybe_identity = identities().may_load(storage, owner.to_string())?;
+    remove_if_released(storage, config, env, maybe_identity)
+}
+
+// Loads the identity holding the name, released identities are removed so the name can be claimed again
+fn load_active_identity_by_name(
+    storage: &mut dyn Storage,
+    config: &Config,
+    env: &Env,
+    name: &str,
+) -> StdResult<Option<Identity>> {
+    let maybe_identity = identities()
+        .idx
+        .name
+        .item(storage, canonical_name(name))?
+        .map(|(_, identity)| identity);
+    remove_if_released(storage, config, env, maybe_identity)
+}
+
+fn remove_if_released(
+    storage: &mut dyn Storage,
+    config: &Config,
+    env: &Env,
+    maybe_identity: Option<Identity>,
+) -> StdResult<Option<Identity>> {
+    match maybe_identity {
+        Some(identity) if identity.is_released(config, env) => {
+            identities().remove(storage, identity.owner.to_string())?;
+            PENDING_TRANSFERS.remove(storage, &identity.owner);
+            Ok(None)
+        }
+        maybe_identity => Ok(maybe_identity),
+    }
+}
+
+pub fn execute_transfer_identity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+) -> Result<Response, ContractError> {
+    let to = deps.api.addr_validate(&to)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let identity = load_active_identity_by_owner(deps.storage, &config, &env, &info.sender)?
+        .ok_or(ContractError::NoIdentity {})?;
+
+    // DAO identities belong to their multisig contract, other contracts look them up by that address
+    if identity.id_type == IdType::Dao {
+        return Err(ContractError::DaoIdentityNotTransferable {});
+    }
+
+    // A new offer replaces any previous pending transfer
+    PENDING_TRANSFERS.save(deps.storage, &info.sender, &to)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_identity")
+        .add_attribute("name", identity.name)
+        .add_attribute("from", info.sender)
+        .add_attribute("to", to))
+}
+
+pub fn execute_accept_identity_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: String,
+) -> Result<Response, ContractError> {
+    let from = deps.api.addr_validate(&from)?;
+
+    let to = PENDING_TRANSFERS.may_load(deps.storage, &from)?.ok_or(
+        ContractError::NoPendingTransfer {
+            from: from.to_string(),
+        },
+    )?;
+
+    if to != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // The recipient can only hold one identity
+    if load_active_identity_by_owner(deps.storage, &config, &env, &info.sender)?.is_some() {
+        return Err(ContractError::AlreadyRegistered {});
+    }
+
+    // The offer lapses with the name once it is released
+    let mut identity = load_active_identity_by_owner(deps.storage, &config, &env, &from)?.ok_or(
+        ContractError::NoPendingTransfer {
+            from: from.to_string(),
+        },
+    )?;
+
+    // Re-key the identity, removing it first frees the name index entry for the new owner
+    identities().remove(deps.storage, from.to_string())?;
+    identity.owner = info.sender.clone();
+    identities().save(deps.storage, identity.owner.to_string(), &identity)?;
+
+    PENDING_TRANSFERS.remove(deps.storage, &from);
+
+    Ok(Response::new().add_event(
+        Event::new("identity_transferred")
+            .add_attribute("name", identity.name)
+            .add_attribute("from", from)
+            .add_attribute("to", info.sender),
+    ))
+}
+
+pub fn execute_cancel_identity_transfer(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if !PENDING_TRANSFERS.has(deps.storage, &info.sender) {
+        return Err(ContractError::NoPendingTransfer {
+            from: info.sender.to_string(),
+        });
+    }
+
+    PENDING_TRANSFERS.remove(deps.storage, &info.sender);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_identity_transfer")
+        .add_attribute("from", info.sender))
+}
+
+pub fn execute_update_profile(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    profile: Option<Profile>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut identity = load_active_identity_by_owner(deps.storage, &config, &env, &info.sender)?
+        .ok_or(ContractError::NoIdentity {})?;
+
+    if identity.status == IdentityStatus::Dissolved {
+        return Err(ContractError::DaoDissolved {});
+    }
+
+    if let Some(profile) = &profile {
+        validate_profile(profile)?;
+    }
+
+    identity.profile = profile;
+    identities().save(deps.storage, identity.owner.to_string(), &identity)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_profile")
+        .add_attribute("name", identity.name)
+        .add_attribute("owner", info.sender))
+}
+
+pub fn execute_register_dao(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     register_dao_msg: RegisterDaoMsg,
 ) -> Result<Response, ContractError> {
     validate_name(&register_dao_msg.dao_name)?;
 
     let config: Config = CONFIG.load(deps.storage)?;
 
+    // Every registration instantiates two contracts, so an address can't register DAOs in bulk
+    let now = env.block.time.seconds();
+    if let Some(last_registration) = LAST_DAO_REGISTRATION.may_load(deps.storage, &info.sender)? {
+        let until = last_registration + config.dao_registration_cooldown;
+        if now < until {
+            return Err(ContractError::DaoRegistrationCooldown { until });
+        }
+    }
+    LAST_DAO_REGISTRATION.save(deps.storage, &info.sender, &now)?;
+
+    let fee = must_pay_fee(&info, config.dao_fee.clone())?;
+
     // Check if requested name is already taken
-    let maybe_name_exists = identities()
-        .idx
-        .name
-        .item(deps.storage, register_dao_msg.dao_name.clone());
+    let maybe_name_exists =
+        load_active_identity_by_name(deps.storage, &config, &env, &register_dao_msg.dao_name);
 
     if maybe_name_exists?.is_some() {
         return Err(ContractError::NameTaken {
@@ -133,6 +475,34 @@ pub fn execute_register_dao(
         });
     }
 
+    let executor = match register_dao_msg.executor {
+        Some(Executor::Only(addr)) => Executor::Only(deps.api.addr_validate(addr.as_str())?),
+        Some(Executor::Member) | None => Executor::Member,
+    };
+    let admin = register_dao_msg
+        .admin
+        .map(|admin| deps.api.addr_validate(&admin))
+        .transpose()?;
+
+    let members = register_dao_msg
+        .members
+        .iter()
+        .map(|member| deps.api.addr_validate(&member.addr))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    PENDING_DAO.save(
+        deps.storage,
+        &PendingDao {
+            executor,
+            multisig_threshold: register_dao_msg.multisig_threshold,
+            admin: admin.clone(),
+            payer: info.sender,
+            fee,
+            dao_template_version: config.dao_template_version,
+            members,
+        },
+    )?;
+
     // Add the governance contract addr to the instantiate msg
     let instantiate_dao_members_msg = dao_members::msg::InstantiateMsg {
         members: register_dao_msg.members,
@@ -145,7 +515,7 @@ pub fn execute_register_dao(
     // Instantiate the DAO contract
     let instantiate_dao_members_wasm_msg: WasmMsg = WasmMsg::Instantiate {
         label: "dao-members".to_string(),
-        admin: None,
+        admin: admin.map(|admin| admin.to_string()),
         code_id: config.dao_members_code_id,
         msg: to_binary(&instantiate_dao_members_msg)?,
         funds: vec![],
@@ -164,6 +534,344 @@ pub fn execute_register_dao(
     Ok(response)
 }
 
+pub fn execute_dissolve_dao(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // DAO identities are owned by their dao-multisig, so only the DAO itself can dissolve
+    let mut identity = identities()
+        .may_load(deps.storage, info.sender.to_string())?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    if identity.id_type != IdType::Dao {
+        return Err(ContractError::Unauthorized {});
+    }
+    if identity.status == IdentityStatus::Dissolved {
+        return Err(ContractError::DaoDissolved {});
+    }
+
+    identity.status = IdentityStatus::Dissolved;
+    identities().save(deps.storage, identity.owner.to_string(), &identity)?;
+
+    if let Some(dao_id) = DAO_IDS.may_load(deps.storage, &info.sender)? {
+        DAOS.remove(deps.storage, dao_id);
+        DAO_IDS.remove(deps.storage, &info.sender);
+    }
+
+    // A DAO dissolved before its first successful proposal forfeits its registration fee
+    let mut msgs = vec![];
+    if let Some(dao_fee) = DAO_FEES.may_load(deps.storage, &info.sender)? {
+        DAO_FEES.remove(deps.storage, &info.sender);
+        let config = CONFIG.load(deps.storage)?;
+        msgs.push(BankMsg::Send {
+            to_address: config.dao_fee_recipient().to_string(),
+            amount: vec![dao_fee.amount],
+        });
+    }
+
+    Ok(Response::new().add_messages(msgs).add_event(
+        Event::new("dao_dissolved")
+            .add_attribute("name", identity.name)
+            .add_attribute("dao", info.sender),
+    ))
+}
+
+pub fn execute_refund_dao_fee(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    dao: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.governance_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let dao = deps.api.addr_validate(&dao)?;
+
+    // Governance calls this for every successful proposal, only the first one refunds anything
+    let dao_fee = match DAO_FEES.may_load(deps.storage, &dao)? {
+        Some(dao_fee) => dao_fee,
+        None => return Ok(Response::new()),
+    };
+    DAO_FEES.remove(deps.storage, &dao);
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: dao_fee.payer.to_string(),
+            amount: vec![dao_fee.amount.clone()],
+        })
+        .add_attribute("action", "refund_dao_fee")
+        .add_attribute("dao", dao)
+        .add_attribute("payer", dao_fee.payer)
+        .add_attribute("amount", dao_fee.amount.to_string()))
+}
+
+pub fn execute_sweep_dao_fee(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    dao: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let dao = deps.api.addr_validate(&dao)?;
+
+    let dao_fee = DAO_FEES
+        .may_load(deps.storage, &dao)?
+        .ok_or(ContractError::NoDaoFee {
+            dao: dao.to_string(),
+        })?;
+
+    // Without a refund period the fee stays refundable until the DAO is dissolved
+    let refundable = match config.dao_fee_refund_period {
+        Some(period) => env.block.time.seconds() < dao_fee.paid_at + period,
+        None => true,
+    };
+    if refundable {
+        return Err(ContractError::DaoFeeRefundable {});
+    }
+
+    DAO_FEES.remove(deps.storage, &dao);
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: config.dao_fee_recipient().to_string(),
+            amount: vec![dao_fee.amount.clone()],
+        })
+        .add_attribute("action", "sweep_dao_fee")
+        .add_attribute("dao", dao)
+        .add_attribute("amount", dao_fee.amount.to_string()))
+}
+
+pub fn execute_member_changed_hook(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hook_msg: MemberChangedHookMsg,
+) -> Result<Response, ContractError> {
+    // Only the dao-members contracts created by this contract are trusted
+    let dao = DAO_MEMBERS_CONTRACTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    for diff in hook_msg.diffs.iter() {
+        let member = deps.api.addr_validate(&diff.key)?;
+        match diff.new {
+            Some(_) => DAO_MEMBERSHIPS.save(deps.storage, (&member, &dao), &Empty {})?,
+            None => DAO_MEMBERSHIPS.remove(deps.storage, (&member, &dao)),
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "member_changed_hook")
+        .add_attribute("dao", dao)
+        .add_attribute("changed", hook_msg.diffs.len().to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update_config_msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner && info.sender != config.governance_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = update_config_msg.owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+    if let Some(governance_addr) = update_config_msg.governance_addr {
+        config.governance_addr = deps.api.addr_validate(&governance_addr)?;
+    }
+    if let Some(name_fees) = update_config_msg.name_fees {
+        config.name_fees = name_fees;
+    }
+    if let Some(name_registration_period) = update_config_msg.name_registration_period {
+        config.name_registration_period = Some(name_registration_period);
+    }
+    if let Some(name_grace_period) = update_config_msg.name_grace_period {
+        config.name_grace_period = name_grace_period;
+    }
+    if let Some(dao_fee) = update_config_msg.dao_fee {
+        config.dao_fee = Some(dao_fee).filter(|dao_fee| !dao_fee.amount.is_zero());
+    }
+    if let Some(dao_fee_recipient) = update_config_msg.dao_fee_recipient {
+        config.dao_fee_recipient = Some(deps.api.addr_validate(&dao_fee_recipient)?);
+    }
+    if let Some(dao_fee_refund_period) = update_config_msg.dao_fee_refund_period {
+        config.dao_fee_refund_period = Some(dao_fee_refund_period);
+    }
+    if let Some(dao_registration_cooldown) = update_config_msg.dao_registration_cooldown {
+        config.dao_registration_cooldown = dao_registration_cooldown;
+    }
+
+    let dao_members_code_id = update_config_msg
+        .dao_members_code_id
+        .unwrap_or(config.dao_members_code_id);
+    let dao_multisig_code_id = update_config_msg
+        .dao_multisig_code_id
+        .unwrap_or(config.dao_multisig_code_id);
+
+    // Existing DAOs keep the version they were created with, only new DAOs use the new template
+    if dao_members_code_id != config.dao_members_code_id
+        || dao_multisig_code_id != config.dao_multisig_code_id
+    {
+        config.dao_members_code_id = dao_members_code_id;
+        config.dao_multisig_code_id = dao_multisig_code_id;
+        config.dao_template_version += 1;
+
+        DAO_TEMPLATES.save(
+            deps.storage,
+            config.dao_template_version,
+            &DaoTemplate {
+                version: config.dao_template_version,
+                dao_members_code_id,
+                dao_multisig_code_id,
+                added_at: env.block.time.seconds(),
+            },
+        )?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute(
+            "dao_template_version",
+            config.dao_template_version.to_string(),
+        ))
+}
+
+pub fn execute_attest(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    subject: String,
+    kind: String,
+    expires: Option<u64>,
+    data_hash: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // DAOs attest through governance proposals, everyone else has to be allow-listed
+    if info.sender != config.governance_addr && !ATTESTATION_ISSUERS.has(deps.storage, &info.sender)
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let subject = deps.api.addr_validate(&subject)?;
+    load_active_identity_by_owner(deps.storage, &config, &env, &subject)?
+        .ok_or(ContractError::NoIdentity {})?;
+
+    if kind.is_empty() {
+        return Err(ContractError::EmptyAttestationKind {});
+    }
+    if kind.len() as u64 > MAX_ATTESTATION_KIND_LENGTH {
+        return Err(ContractError::AttestationFieldTooLong {
+            field: "kind".to_string(),
+            length: kind.len() as u64,
+            max_length: MAX_ATTESTATION_KIND_LENGTH,
+        });
+    }
+    if let Some(data_hash) = &data_hash {
+        if data_hash.len() as u64 > MAX_DATA_HASH_LENGTH {
+            return Err(ContractError::AttestationFieldTooLong {
+                field: "data_hash".to_string(),
+                length: data_hash.len() as u64,
+                max_length: MAX_DATA_HASH_LENGTH,
+            });
+        }
+    }
+
+    let attestation = Attestation {
+        subject,
+        issuer: info.sender,
+        kind,
+        issued_at: env.block.time.seconds(),
+        expires,
+        data_hash,
+    };
+    if attestation.is_expired(&env) {
+        return Err(ContractError::AttestationExpired {
+            expires: expires.unwrap_or_default(),
+        });
+    }
+
+    attestations().save(
+        deps.storage,
+        (
+            attestation.subject.clone(),
+            attestation.issuer.clone(),
+            attestation.kind.clone(),
+        ),
+        &attestation,
+    )?;
+
+    Ok(Response::new().add_event(
+        Event::new("attested")
+            .add_attribute("subject", attestation.subject)
+            .add_attribute("issuer", attestation.issuer)
+            .add_attribute("kind", attestation.kind),
+    ))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    subject: String,
+    kind: String,
+) -> Result<Response, ContractError> {
+    let subject = deps.api.addr_validate(&subject)?;
+    let key = (subject.clone(), info.sender.clone(), kind.clone());
+
+    if !attestations().has(deps.storage, key.clone()) {
+        return Err(ContractError::NoAttestation {
+            subject: subject.to_string(),
+            kind,
+        });
+    }
+    attestations().remove(deps.storage, key)?;
+
+    Ok(Response::new().add_event(
+        Event::new("attestation_revoked")
+            .add_attribute("subject", subject)
+            .add_attribute("issuer", info.sender)
+            .add_attribute("kind", kind),
+    ))
+}
+
+pub fn execute_update_attestation_issuers(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.owner && info.sender != config.governance_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Removing an issuer keeps the attestations it already issued
+    for issuer in add.iter() {
+        let issuer = deps.api.addr_validate(issuer)?;
+        ATTESTATION_ISSUERS.save(deps.storage, &issuer, &Empty {})?;
+    }
+    for issuer in remove.iter() {
+        let issuer = deps.api.addr_validate(issuer)?;
+        ATTESTATION_ISSUERS.remove(deps.storage, &issuer);
+    }
+
+    Ok(Response::new().add_attribute("action", "update_attestation_issuers"))
+}
+
 #[entry_point]
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
@@ -188,17 +896,21 @@ fn instantiate_dao_members_reply(
         .querier
         .query_wasm_smart(&dao_members_addr, &dao_members::msg::QueryMsg::Config {})?;
 
+    let pending_dao = PENDING_DAO.load(deps.storage)?;
+
     let dao_multisig_instantiate_msg = dao_multisig::msg::InstantiateMsg {
         group_addr: dao_members_addr.to_string(),
-        executor: Some(Executor::Member),
+        executor: Some(pending_dao.executor),
         max_voting_period: dao_members_config.max_voting_period,
-        threshold: dao_members_config.threshold,
+        threshold: pending_dao
+            .multisig_threshold
+            .unwrap_or(dao_members_config.threshold),
         dao_name: dao_members_config.dao_name,
     };
 
     let instantiate_dao_multisig_message: WasmMsg = WasmMsg::Instantiate {
         label: "dao-multisig".to_string(),
-        admin: None,
+        admin: pending_dao.admin.map(|admin| admin.to_string()),
         code_id: config.dao_multisig_code_id,
         msg: to_binary(&dao_multisig_instantiate_msg)?,
         funds: vec![],
@@ -217,7 +929,7 @@ fn instantiate_dao_members_reply(
 
 fn instantiate_dao_multisig_reply(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     msg: Reply,
 ) -> Result<Response, ContractError> {
     let reply = parse_reply_instantiate_data(msg).unwrap();
@@ -228,13 +940,24 @@ fn instantiate_dao_multisig_reply(
 
     let dao_name = dao_multisig_config.dao_name;
 
+    // Register as hook to keep track of the members while this contract is still the admin
+    let add_hook_msg = dao_members::msg::ExecuteMsg::AddHook {
+        addr: env.contract.address.to_string(),
+    };
+
+    let add_hook_msg_wasm = WasmMsg::Execute {
+        contract_addr: dao_multisig_config.dao_members_addr.to_string(),
+        msg: to_binary(&add_hook_msg)?,
+        funds: vec![],
+    };
+
     // Update the admin of dao-members to the dao-multisig-addr
     let update_admin_msg = dao_members::msg::ExecuteMsg::UpdateAdmin {
         admin: Some(dao_multisig_addr.to_string()),
     };
 
     let update_admin_msg_wasm = WasmMsg::Execute {
-        contract_addr: dao_multisig_config.dao_members_addr.into(),
+        contract_addr: dao_multisig_config.dao_members_addr.to_string(),
         msg: to_binary(&update_admin_msg)?,
         funds: vec![],
     };
@@ -244,6 +967,9 @@ fn instantiate_dao_multisig_reply(
         owner: dao_multisig_addr.clone(),
         name: dao_name,
         id_type: IdType::Dao,
+        profile: None,
+        expires: None,
+        status: IdentityStatus::Active,
     };
 
     identities().save(deps.storage, identity.owner.to_string(), &identity)?;
@@ -251,22 +977,121 @@ fn instantiate_dao_multisig_reply(
     // This is used to allow paginating through all daos
     let dao_id = next_dao_id(deps.storage)?;
     DAOS.save(deps.storage, dao_id, &dao_multisig_addr)?;
+    DAO_IDS.save(deps.storage, &dao_multisig_addr, &dao_id)?;
+
+    let pending_dao = PENDING_DAO.load(deps.storage)?;
+    PENDING_DAO.remove(deps.storage);
+
+    DAO_TEMPLATE_VERSIONS.save(
+        deps.storage,
+        &dao_multisig_addr,
+        &pending_dao.dao_template_version,
+    )?;
+
+    DAO_MEMBERS_CONTRACTS.save(
+        deps.storage,
+        &dao_multisig_config.dao_members_addr,
+        &dao_multisig_addr,
+    )?;
+    for member in pending_dao.members.iter() {
+        DAO_MEMBERSHIPS.save(deps.storage, (member, &dao_multisig_addr), &Empty {})?;
+    }
+
+    if let Some(fee) = pending_dao.fee {
+        DAO_FEES.save(
+            deps.storage,
+            &dao_multisig_addr,
+            &DaoFee {
+                payer: pending_dao.payer,
+                amount: fee,
+                paid_at: env.block.time.seconds(),
+            },
+        )?;
+    }
 
     Ok(Response::new()
         .set_data(to_binary(&InstantiateResponse { dao_multisig_addr })?)
+        .add_message(add_hook_msg_wasm)
         .add_message(update_admin_msg_wasm))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::GetIdentityByOwner { owner } => to_binary(&query_identity_by_owner(deps, owner)?),
-        QueryMsg::GetIdentityByName { name } => to_binary(&query_identity_by_name(deps, name)?),
+        QueryMsg::GetIdentityByOwner { owner } => {
+            to_binary(&query_identity_by_owner(deps, env, owner)?)
+        }
+        QueryMsg::GetIdentityByName { name } => {
+            to_binary(&query_identity_by_name(deps, env, name)?)
+        }
         QueryMsg::Daos {
             start_after,
             limit,
             order,
         } => to_binary(&daos(deps, start_after, limit, order)?),
+        QueryMsg::PendingIdentityTransfer { owner } => {
+            to_binary(&query_pending_identity_transfer(deps, owner)?)
+        }
+        QueryMsg::Identities {
+            start_after,
+            limit,
+            id_type,
+        } => to_binary(&query_identities(deps, env, start_after, limit, id_type)?),
+        QueryMsg::NamesStartingWith {
+            prefix,
+            start_after,
+            limit,
+        } => to_binary(&query_names_starting_with(
+            deps,
+            env,
+            prefix,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::IdentitiesByOwners { owners } => {
+            to_binary(&query_identities_by_owners(deps, env, owners)?)
+        }
+        QueryMsg::CanonicalName { name } => to_binary(&query_canonical_name(name)?),
+        QueryMsg::DaoFee { dao } => to_binary(&query_dao_fee(deps, dao)?),
+        QueryMsg::DaoTemplates { start_after, limit } => {
+            to_binary(&query_dao_templates(deps, start_after, limit)?)
+        }
+        QueryMsg::DaoTemplate { dao } => to_binary(&query_dao_template(deps, dao)?),
+        QueryMsg::DaosOfMember {
+            member,
+            start_after,
+            limit,
+        } => to_binary(&query_daos_of_member(deps, member, start_after, limit)?),
+        QueryMsg::Attestation {
+            subject,
+            issuer,
+            kind,
+        } => to_binary(&query_attestation(deps, env, subject, issuer, kind)?),
+        QueryMsg::AttestationsBySubject {
+            subject,
+            start_after,
+            limit,
+        } => to_binary(&query_attestations_by_subject(
+            deps,
+            env,
+            subject,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AttestationsByIssuer {
+            issuer,
+            start_after,
+            limit,
+        } => to_binary(&query_attestations_by_issuer(
+            deps,
+            env,
+            issuer,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AttestationIssuers { start_after, limit } => {
+            to_binary(&query_attestation_issuers(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -291,20 +1116,142 @@ pub fn daos(
         .collect::<StdResult<_>>()?;
     Ok(DaosResponse { daos })
 }
-fn query_identity_by_owner(deps: Deps, owner: String) -> StdResult<GetIdentityByOwnerResponse> {
-    let maybe_identity = identities().may_load(deps.storage, owner)?;
+fn query_identity_by_owner(
+    deps: Deps,
+    env: Env,
+    owner: String,
+) -> StdResult<GetIdentityByOwnerResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let maybe_identity = identities()
+        .may_load(deps.storage, owner)?
+        .filter(|identity| !identity.is_released(&config, &env));
 
     Ok(GetIdentityByOwnerResponse {
         identity: maybe_identity,
     })
 }
 
-fn query_identity_by_name(deps: Deps, name: String) -> StdResult<GetIdentityByNameResponse> {
-    let maybe_identity_result = identities().idx.name.item(deps.storage, name);
+fn query_identities(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    id_type: Option<IdType>,
+) -> StdResult<IdentitiesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    // Filtering by type walks the type index, so only identities of that type are scanned
+    let identities = match id_type {
+        Some(id_type) => identities()
+            .idx
+            .id_type
+            .prefix(id_type.as_str().to_string())
+            .range(deps.storage, start, None, Order::Ascending),
+        None => identities().range(deps.storage, start, None, Order::Ascending),
+    }
+    .map(|item| item.map(|(_, identity)| identity))
+    .filter(|item| {
+        item.as_ref()
+            .map_or(true, |identity| !identity.is_released(&config, &env))
+    })
+    .take(limit)
+    .collect::<StdResult<_>>()?;
+
+    Ok(IdentitiesResponse { identities })
+}
+
+fn query_names_starting_with(
+    deps: Deps,
+    env: Env,
+    prefix: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<IdentitiesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // The name index is ordered by canonical name, so all matches follow the prefix itself
+    let prefix = canonical_name(&prefix);
+    let start = match start_after.map(|start_after| canonical_name(&start_after)) {
+        Some(start_after) if start_after > prefix => Bound::exclusive(start_after),
+        _ => Bound::inclusive(prefix.clone()),
+    };
+
+    let identities = identities()
+        .idx
+        .name
+        .range(deps.storage, Some(start), None, Order::Ascending)
+        .map(|item| item.map(|(_, identity)| identity))
+        .take_while(|item| {
+            item.as_ref().map_or(true, |identity| {
+                canonical_name(&identity.name).starts_with(&prefix)
+            })
+        })
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |identity| !identity.is_released(&config, &env))
+        })
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(IdentitiesResponse { identities })
+}
+
+fn query_identities_by_owners(
+    deps: Deps,
+    env: Env,
+    owners: Vec<String>,
+) -> StdResult<IdentitiesByOwnersResponse> {
+    if owners.len() > MAX_LIMIT as usize {
+        return Err(StdError::generic_err(format!(
+            "Too many owners (count {} max {})",
+            owners.len(),
+            MAX_LIMIT
+        )));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let identities = owners
+        .into_iter()
+        .map(|owner| {
+            let identity = identities()
+                .may_load(deps.storage, owner.clone())?
+                .filter(|identity| !identity.is_released(&config, &env));
+            Ok(OwnerIdentity { owner, identity })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(IdentitiesByOwnersResponse { identities })
+}
+
+fn query_pending_identity_transfer(
+    deps: Deps,
+    owner: String,
+) -> StdResult<PendingIdentityTransferResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let to = PENDING_TRANSFERS.may_load(deps.storage, &owner)?;
+
+    Ok(PendingIdentityTransferResponse { to })
+}
+
+fn query_identity_by_name(
+    deps: Deps,
+    env: Env,
+    name: String,
+) -> StdResult<GetIdentityByNameResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let maybe_identity_result = identities()
+        .idx
+        .name
+        .item(deps.storage, canonical_name(&name));
 
+    // Released names are free to be claimed, so they no longer resolve to their previous owner
     let maybe_identity = match maybe_identity_result? {
-        Some(thing) => Some(thing.1),
-        None => None,
+        Some(thing) if !thing.1.is_released(&config, &env) => Some(thing.1),
+        _ => None,
     };
 
     Ok(GetIdentityByNameResponse {
@@ -312,12 +1259,207 @@ fn query_identity_by_name(deps: Deps, name: String) -> StdResult<GetIdentityByNa
     })
 }
 
+fn query_attestation(
+    deps: Deps,
+    env: Env,
+    subject: String,
+    issuer: String,
+    kind: String,
+) -> StdResult<AttestationResponse> {
+    let subject = deps.api.addr_validate(&subject)?;
+    let issuer = deps.api.addr_validate(&issuer)?;
+
+    let attestation = attestations()
+        .may_load(deps.storage, (subject, issuer, kind))?
+        .filter(|attestation| !attestation.is_expired(&env));
+
+    Ok(AttestationResponse { attestation })
+}
+
+fn query_attestations_by_subject(
+    deps: Deps,
+    env: Env,
+    subject: String,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<AttestationsResponse> {
+    let subject = deps.api.addr_validate(&subject)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|(issuer, kind)| -> StdResult<_> {
+            Ok(Bound::exclusive((deps.api.addr_validate(&issuer)?, kind)))
+        })
+        .transpose()?;
+
+    let attestations = attestations()
+        .sub_prefix(subject)
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| item.map(|(_, attestation)| attestation))
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |attestation| !attestation.is_expired(&env))
+        })
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(AttestationsResponse { attestations })
+}
+
+fn query_attestations_by_issuer(
+    deps: Deps,
+    env: Env,
+    issuer: String,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<AttestationsResponse> {
+    let issuer = deps.api.addr_validate(&issuer)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|(subject, kind)| -> StdResult<_> {
+            Ok(Bound::exclusive((
+                deps.api.addr_validate(&subject)?,
+                issuer.clone(),
+                kind,
+            )))
+        })
+        .transpose()?;
+
+    let attestations = attestations()
+        .idx
+        .issuer
+        .prefix(issuer)
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| item.map(|(_, attestation)| attestation))
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |attestation| !attestation.is_expired(&env))
+        })
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(AttestationsResponse { attestations })
+}
+
+fn query_attestation_issuers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AttestationIssuersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|issuer| deps.api.addr_validate(&issuer))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let issuers = ATTESTATION_ISSUERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(AttestationIssuersResponse { issuers })
+}
+
+fn query_dao_fee(deps: Deps, dao: String) -> StdResult<DaoFeeResponse> {
+    let dao = deps.api.addr_validate(&dao)?;
+    let fee = DAO_FEES.may_load(deps.storage, &dao)?;
+
+    Ok(DaoFeeResponse { fee })
+}
+
+fn query_dao_templates(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DaoTemplatesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let templates = DAO_TEMPLATES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, template)| template))
+        .collect::<StdResult<_>>()?;
+
+    Ok(DaoTemplatesResponse { templates })
+}
+
+fn query_dao_template(deps: Deps, dao: String) -> StdResult<DaoTemplateResponse> {
+    let dao = deps.api.addr_validate(&dao)?;
+
+    let template = DAO_TEMPLATE_VERSIONS
+        .may_load(deps.storage, &dao)?
+        .map(|version| DAO_TEMPLATES.load(deps.storage, version))
+        .transpose()?;
+
+    Ok(DaoTemplateResponse { template })
+}
+
+fn query_daos_of_member(
+    deps: Deps,
+    member: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DaosOfMemberResponse> {
+    let member = deps.api.addr_validate(&member)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|dao| deps.api.addr_validate(&dao))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    // Memberships of dissolved DAOs are kept, they are skipped here instead
+    let daos = DAO_MEMBERSHIPS
+        .prefix(&member)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok(dao) => DAO_IDS.has(deps.storage, dao),
+            Err(_) => true,
+        })
+        .take(limit)
+        .collect::<StdResult<_>>()?;
+
+    Ok(DaosOfMemberResponse { daos })
+}
+
+fn query_canonical_name(name: String) -> StdResult<CanonicalNameResponse> {
+    validate_name(&name).map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(CanonicalNameResponse {
+        canonical_name: canonical_name(&name),
+    })
+}
+
 // let's not import a regexp library and just do these checks by hand
 fn invalid_char(c: char) -> bool {
-    let is_valid = c.is_digit(10) || c.is_ascii_lowercase(); // we may allow these in the future: || (c == '.' || c == '-' || c == '_');
+    let is_valid = c.is_digit(10) || c.is_ascii_lowercase() || is_separator(c);
     !is_valid
 }
 
+fn is_separator(c: char) -> bool {
+    c == '.' || c == '-' || c == '_'
+}
+
+// Separators have to sit between other characters, otherwise names could be padded
+// or stretched to look like an existing one
+fn validate_separators(name: &str) -> Result<(), ContractError> {
+    if name.starts_with(is_separator) || name.ends_with(is_separator) {
+        return Err(ContractError::SeparatorAtEdge {
+            name: name.to_string(),
+        });
+    }
+
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_separator(c) && chars.peek().map_or(false, |next| is_separator(*next)) {
+            return Err(ContractError::ConsecutiveSeparators {
+                name: name.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// validate_name returns an error if the name is invalid
 /// (we require 3-20 lowercase ascii letters, numbers, or . - _)
 fn validate_name(name: &str) -> Result<(), ContractError> {
@@ -334,7 +1476,7 @@ fn validate_name(name: &str) -> Result<(), ContractError> {
         })
     } else {
         match name.find(invalid_char) {
-            None => Ok(()),
+            None => validate_separators(name),
             Some(bytepos_invalid_char_start) => {
                 let c = name[bytepos_invalid_char_start..].chars().next().unwrap();
                 Err(ContractError::InvalidCharacter { c })
@@ -342,3 +1484,52 @@ fn validate_name(name: &str) -> Result<(), ContractError> {
         }
     }
 }
+
+fn validate_length(field: &str, value: &str, max_length: u64) -> Result<(), ContractError> {
+    let length = value.len() as u64;
+    if length > max_length {
+        return Err(ContractError::ProfileFieldTooLong {
+            field: field.to_string(),
+            length,
+            max_length,
+        });
+    }
+    Ok(())
+}
+
+/// validate_profile returns an error if one of the profile fields exceeds its size limit
+fn validate_profile(profile: &Profile) -> Result<(), ContractError> {
+    let optional_fields = [
+        (
+            "display_name",
+            &profile.display_name,
+            MAX_DISPLAY_NAME_LENGTH,
+        ),
+        ("avatar_uri", &profile.avatar_uri, MAX_URI_LENGTH),
+        ("website", &profile.website, MAX_URI_LENGTH),
+        ("bio", &profile.bio, MAX_BIO_LENGTH),
+    ];
+    for (field, value, max_length) in optional_fields {
+        if let Some(value) = value {
+            validate_length(field, value, max_length)?;
+        }
+    }
+
+    let count = profile.socials.len() as u64;
+    if count > MAX_SOCIALS {
+        return Err(ContractError::TooManySocials {
+            count,
+            max: MAX_SOCIALS,
+        });
+    }
+    for social in profile.socials.iter() {
+        validate_length(
+            "socials.platform",
+            &social.platform,
+            MAX_SOCIAL_PLATFORM_LENGTH,
+        )?;
+        validate_length("socials.handle", &social.handle, MAX_SOCIAL_HANDLE_LENGTH)?;
+    }
+
+    Ok(())
+}
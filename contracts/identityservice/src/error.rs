@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,6 +7,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Version(#[from] cw2::VersionError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -24,6 +31,65 @@ pub enum ContractError {
     #[error("Invalid character(char {c}")]
     InvalidCharacter { c: char },
 
+    #[error("Name cannot start or end with a separator (name {name})")]
+    SeparatorAtEdge { name: String },
+
+    #[error("Name cannot contain consecutive separators (name {name})")]
+    ConsecutiveSeparators { name: String },
+
     #[error("InvalidThresholdPercentage max is 100 (current {current})")]
     InvalidThresholdPercentage { current: u64 },
+
+    #[error("Address has no identity registered.")]
+    NoIdentity {},
+
+    #[error("DAO identities can't be transferred.")]
+    DaoIdentityNotTransferable {},
+
+    #[error("No pending identity transfer (from {from})")]
+    NoPendingTransfer { from: String },
+
+    #[error("Profile field too long (field {field} length {length} max_length {max_length})")]
+    ProfileFieldTooLong {
+        field: String,
+        length: u64,
+        max_length: u64,
+    },
+
+    #[error("Too many socials (count {count} max {max})")]
+    TooManySocials { count: u64, max: u64 },
+
+    #[error("Insufficient fee (required {required})")]
+    InsufficientFee { required: String },
+
+    #[error("Name does not expire (name {name})")]
+    NameDoesNotExpire { name: String },
+
+    #[error("DAO is dissolved")]
+    DaoDissolved {},
+
+    #[error("DAO registration cooldown (next registration possible at {until})")]
+    DaoRegistrationCooldown { until: u64 },
+
+    #[error("No DAO fee held (dao {dao})")]
+    NoDaoFee { dao: String },
+
+    #[error("DAO fee can still be refunded")]
+    DaoFeeRefundable {},
+
+    #[error("Attestation kind cannot be empty")]
+    EmptyAttestationKind {},
+
+    #[error("Attestation field too long (field {field} length {length} max_length {max_length})")]
+    AttestationFieldTooLong {
+        field: String,
+        length: u64,
+        max_length: u64,
+    },
+
+    #[error("Attestation would be expired already (expires {expires})")]
+    AttestationExpired { expires: u64 },
+
+    #[error("No attestation (subject {subject} kind {kind})")]
+    NoAttestation { subject: String, kind: String },
 }
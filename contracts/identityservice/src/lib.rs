@@ -4,5 +4,6 @@ pub mod msg;
 #[cfg(any(test, feature = "tests"))]
 pub mod multitest;
 pub mod state;
+pub mod upgrades;
 
 pub use crate::error::ContractError;
@@ -1,10 +1,11 @@
-use cosmwasm_std::Addr;
-use cw4::Member;
-use cw_utils::Duration;
+use cosmwasm_std::{Addr, Binary, Coin};
+use cw4::{Member, MemberChangedHookMsg};
+use cw_utils::{Duration, Threshold};
+use dao_multisig::state::Executor;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::state::Identity;
+use crate::state::{Attestation, DaoFee, DaoTemplate, IdType, Identity, NameFee, Profile};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct Voter {
@@ -17,8 +18,26 @@ pub struct InstantiateMsg {
     pub dao_members_code_id: u64,
     pub dao_multisig_code_id: u64,
     pub governance_addr: Addr,
+    #[serde(default)]
+    pub name_fees: Vec<NameFee>,
+    #[serde(default)]
+    pub name_registration_period: Option<u64>,
+    #[serde(default)]
+    pub name_grace_period: u64,
+    #[serde(default)]
+    pub dao_fee: Option<Coin>,
+    #[serde(default)]
+    pub dao_fee_recipient: Option<Addr>,
+    #[serde(default)]
+    pub dao_fee_refund_period: Option<u64>,
+    #[serde(default)]
+    pub dao_registration_cooldown: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct RegisterDaoMsg {
@@ -26,13 +45,88 @@ pub struct RegisterDaoMsg {
     pub dao_name: String,
     pub threshold_percentage: u64,
     pub max_voting_period: Duration,
+    // Who can execute passed proposals of the dao-multisig, defaults to any member
+    #[serde(default)]
+    pub executor: Option<Executor>,
+    // Threshold of the dao-multisig, defaults to the dao-members threshold
+    #[serde(default)]
+    pub multisig_threshold: Option<Threshold>,
+    // Admin of the dao-members and dao-multisig contracts, they can't be migrated without one
+    #[serde(default)]
+    pub admin: Option<String>,
+}
+
+// Fields left out keep their current value
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UpdateConfigMsg {
+    pub owner: Option<String>,
+    pub governance_addr: Option<String>,
+    pub dao_members_code_id: Option<u64>,
+    pub dao_multisig_code_id: Option<u64>,
+    // Names registered before keep their expiry, the new period applies from their next renewal
+    pub name_fees: Option<Vec<NameFee>>,
+    pub name_registration_period: Option<u64>,
+    pub name_grace_period: Option<u64>,
+    // A zero amount removes the DAO fee. Fees already held are refunded or swept under the
+    // refund period and recipient in effect at that time
+    pub dao_fee: Option<Coin>,
+    pub dao_fee_recipient: Option<String>,
+    pub dao_fee_refund_period: Option<u64>,
+    pub dao_registration_cooldown: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    RegisterUser { name: String },
+    RegisterUser {
+        name: String,
+    },
     RegisterDao(RegisterDaoMsg),
+    // Offers the sender's identity to `to`, the transfer only happens once `to` accepts it
+    TransferIdentity {
+        to: String,
+    },
+    AcceptIdentityTransfer {
+        from: String,
+    },
+    CancelIdentityTransfer {},
+    // Replaces the profile of the sender's identity, `None` removes it
+    UpdateProfile {
+        profile: Option<Profile>,
+    },
+    // Extends the registration of the sender's name by another registration period
+    RenewName {},
+    // Dissolves the DAO of the sending dao-multisig
+    DissolveDao {},
+    // Refunds the registration fee of the DAO, called by governance when a proposal succeeds
+    RefundDaoFee {
+        dao: String,
+    },
+    // Sends a registration fee that was not refunded in time to the fee recipient
+    SweepDaoFee {
+        dao: String,
+    },
+    // Called by the dao-members contracts of registered DAOs whenever their members change
+    MemberChangedHook(MemberChangedHookMsg),
+    // Can be called by the owner or governance, new code ids add a new DAO template version
+    UpdateConfig(UpdateConfigMsg),
+    // Issues or replaces an attestation of the sender about `subject`
+    Attest {
+        subject: String,
+        kind: String,
+        expires: Option<u64>,
+        data_hash: Option<Binary>,
+    },
+    // Removes an attestation the sender issued
+    Revoke {
+        subject: String,
+        kind: String,
+    },
+    UpdateAttestationIssuers {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -56,6 +150,67 @@ pub enum QueryMsg {
         limit: Option<u32>,
         order: Option<Ordering>,
     },
+    PendingIdentityTransfer {
+        owner: String,
+    },
+    // Lists identities ordered by owner address
+    Identities {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        id_type: Option<IdType>,
+    },
+    // Lists identities whose name starts with `prefix`, ordered by name
+    NamesStartingWith {
+        prefix: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    IdentitiesByOwners {
+        owners: Vec<String>,
+    },
+    // Returns the form of a name that is checked for uniqueness
+    CanonicalName {
+        name: String,
+    },
+    DaoFee {
+        dao: String,
+    },
+    DaoTemplates {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Returns the template the DAO was created with
+    DaoTemplate {
+        dao: String,
+    },
+    // Returns the active DAOs (dao-multisig addresses) the address is a member of
+    DaosOfMember {
+        member: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // Returns the attestation if it has not expired
+    Attestation {
+        subject: String,
+        issuer: String,
+        kind: String,
+    },
+    // Lists the attestations of a subject that have not expired, start_after is (issuer, kind)
+    AttestationsBySubject {
+        subject: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    // Lists the attestations of an issuer that have not expired, start_after is (subject, kind)
+    AttestationsByIssuer {
+        issuer: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    AttestationIssuers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -69,6 +224,80 @@ pub struct GetIdentityByNameResponse {
     pub identity: Option<Identity>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct IdentitiesResponse {
+    pub identities: Vec<Identity>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct IdentitiesByOwnersResponse {
+    // One entry per requested owner, in the order they were requested
+    pub identities: Vec<OwnerIdentity>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct OwnerIdentity {
+    pub owner: String,
+    pub identity: Option<Identity>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AttestationResponse {
+    pub attestation: Option<Attestation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AttestationsResponse {
+    pub attestations: Vec<Attestation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AttestationIssuersResponse {
+    pub issuers: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoFeeResponse {
+    pub fee: Option<DaoFee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoTemplatesResponse {
+    pub templates: Vec<DaoTemplate>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoTemplateResponse {
+    pub template: Option<DaoTemplate>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaosOfMemberResponse {
+    pub daos: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CanonicalNameResponse {
+    pub canonical_name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PendingIdentityTransferResponse {
+    pub to: Option<Addr>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct DaosResponse {
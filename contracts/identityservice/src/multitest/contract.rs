@@ -1,12 +1,16 @@
-use cosmwasm_std::{Addr, StdResult};
+use cosmwasm_std::{Addr, Binary, Coin, StdResult};
 use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
 use cw_utils::Duration;
 
-use crate::contract::{execute, instantiate, query, reply};
+use crate::contract::{execute, instantiate, migrate, query, reply};
 use crate::msg::{
-    DaosResponse, ExecuteMsg, GetIdentityByNameResponse, GetIdentityByOwnerResponse,
-    InstantiateMsg, Ordering, QueryMsg, RegisterDaoMsg,
+    AttestationIssuersResponse, AttestationResponse, AttestationsResponse, CanonicalNameResponse,
+    DaoFeeResponse, DaoTemplateResponse, DaoTemplatesResponse, DaosOfMemberResponse, DaosResponse,
+    ExecuteMsg, GetIdentityByNameResponse, GetIdentityByOwnerResponse, IdentitiesByOwnersResponse,
+    IdentitiesResponse, InstantiateMsg, MigrateMsg, Ordering, PendingIdentityTransferResponse,
+    QueryMsg, RegisterDaoMsg, UpdateConfigMsg,
 };
+use crate::state::{IdType, Profile};
 use crate::ContractError;
 
 #[derive(Debug, Clone)]
@@ -18,7 +22,9 @@ impl IdentityserviceContract {
     }
 
     pub fn store_code(app: &mut App) -> u64 {
-        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_reply(reply)
+            .with_migrate(migrate);
         app.store_code(Box::new(contract))
     }
 
@@ -33,38 +39,99 @@ impl IdentityserviceContract {
         dao_multisig_code_id: u64,
         governance_addr: Addr,
     ) -> StdResult<Self> {
-        app.instantiate_contract(
+        Self::instantiate_with_msg(
+            app,
             code_id,
-            sender.clone(),
+            sender,
+            label,
             &InstantiateMsg {
                 owner: governance_addr.clone(),
                 dao_members_code_id,
                 dao_multisig_code_id,
                 governance_addr,
+                name_fees: vec![],
+                name_registration_period: None,
+                name_grace_period: 0,
+                dao_fee: None,
+                dao_fee_recipient: None,
+                dao_fee_refund_period: None,
+                dao_registration_cooldown: 0,
             },
+        )
+    }
+
+    #[track_caller]
+    pub fn instantiate_with_msg(
+        app: &mut App,
+        code_id: u64,
+        sender: &Addr,
+        label: &str,
+        msg: &InstantiateMsg,
+    ) -> StdResult<Self> {
+        app.instantiate_contract(
+            code_id,
+            sender.clone(),
+            msg,
             &[],
             label,
-            None,
+            Some(sender.to_string()),
         )
         .map(IdentityserviceContract)
         .map_err(|err| err.downcast().unwrap())
     }
 
+    #[track_caller]
+    pub fn migrate(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        new_code_id: u64,
+    ) -> Result<AppResponse, ContractError> {
+        app.migrate_contract(sender.clone(), self.0.clone(), &MigrateMsg {}, new_code_id)
+            .map_err(|err| err.downcast().unwrap())
+    }
+
     #[track_caller]
     pub fn register_user(
         &self,
         app: &mut App,
         sender: &Addr,
         name: String,
+    ) -> Result<AppResponse, ContractError> {
+        self.register_user_with_funds(app, sender, name, &[])
+    }
+
+    #[track_caller]
+    pub fn register_user_with_funds(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        name: String,
+        funds: &[Coin],
     ) -> Result<AppResponse, ContractError> {
         app.execute_contract(
             sender.clone(),
             self.0.clone(),
             &ExecuteMsg::RegisterUser { name },
-            &[],
+            funds,
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn renew_name(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        funds: &[Coin],
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RenewName {},
+            funds,
         )
         .map_err(|err| err.downcast().unwrap())
-        // .map(|_| ())
     }
 
     #[track_caller]
@@ -77,19 +144,250 @@ impl IdentityserviceContract {
         threshold_percentage: u64,
         max_voting_period: Duration,
     ) -> Result<AppResponse, ContractError> {
-        app.execute_contract(
-            sender.clone(),
-            self.0.clone(),
-            &ExecuteMsg::RegisterDao(RegisterDaoMsg {
+        self.register_dao_with_msg(
+            app,
+            sender,
+            &[],
+            RegisterDaoMsg {
                 members,
                 dao_name,
                 max_voting_period,
                 threshold_percentage,
-            }),
+                executor: None,
+                multisig_threshold: None,
+                admin: None,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn register_dao_with_msg(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        funds: &[Coin],
+        register_dao_msg: RegisterDaoMsg,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RegisterDao(register_dao_msg),
+            funds,
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn refund_dao_fee(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        dao: String,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RefundDaoFee { dao },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn sweep_dao_fee(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        dao: String,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::SweepDaoFee { dao },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn update_config(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        owner: Option<String>,
+        governance_addr: Option<String>,
+        dao_members_code_id: Option<u64>,
+        dao_multisig_code_id: Option<u64>,
+    ) -> Result<AppResponse, ContractError> {
+        self.update_config_with_msg(
+            app,
+            sender,
+            UpdateConfigMsg {
+                owner,
+                governance_addr,
+                dao_members_code_id,
+                dao_multisig_code_id,
+                ..UpdateConfigMsg::default()
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn update_config_with_msg(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        update_config_msg: UpdateConfigMsg,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateConfig(update_config_msg),
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn transfer_identity(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        to: String,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::TransferIdentity { to },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn accept_identity_transfer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        from: String,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::AcceptIdentityTransfer { from },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn cancel_identity_transfer(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::CancelIdentityTransfer {},
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn update_profile(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        profile: Option<Profile>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateProfile { profile },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn dissolve_dao(&self, app: &mut App, sender: &Addr) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::DissolveDao {},
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn attest(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        subject: String,
+        kind: String,
+        expires: Option<u64>,
+        data_hash: Option<Binary>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Attest {
+                subject,
+                kind,
+                expires,
+                data_hash,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn revoke(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        subject: String,
+        kind: String,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Revoke { subject, kind },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+    }
+
+    #[track_caller]
+    pub fn update_attestation_issuers(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<AppResponse, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateAttestationIssuers { add, remove },
             &[],
         )
         .map_err(|err| err.downcast().unwrap())
-        // .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn query_pending_identity_transfer(
+        &self,
+        app: &mut App,
+        owner: String,
+    ) -> StdResult<PendingIdentityTransferResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::PendingIdentityTransfer { owner })
     }
 
     #[track_caller]
@@ -129,6 +427,172 @@ impl IdentityserviceContract {
         app.wrap()
             .query_wasm_smart(self.0.clone(), &QueryMsg::GetIdentityByName { name })
     }
+
+    #[track_caller]
+    pub fn query_identities(
+        &self,
+        app: &mut App,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        id_type: Option<IdType>,
+    ) -> StdResult<IdentitiesResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Identities {
+                start_after,
+                limit,
+                id_type,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_names_starting_with(
+        &self,
+        app: &mut App,
+        prefix: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<IdentitiesResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::NamesStartingWith {
+                prefix,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_identities_by_owners(
+        &self,
+        app: &mut App,
+        owners: Vec<String>,
+    ) -> StdResult<IdentitiesByOwnersResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::IdentitiesByOwners { owners })
+    }
+
+    #[track_caller]
+    pub fn query_dao_fee(&self, app: &mut App, dao: String) -> StdResult<DaoFeeResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::DaoFee { dao })
+    }
+
+    #[track_caller]
+    pub fn query_dao_templates(
+        &self,
+        app: &mut App,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<DaoTemplatesResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::DaoTemplates { start_after, limit },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_dao_template(&self, app: &mut App, dao: String) -> StdResult<DaoTemplateResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::DaoTemplate { dao })
+    }
+
+    #[track_caller]
+    pub fn query_daos_of_member(
+        &self,
+        app: &mut App,
+        member: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<DaosOfMemberResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::DaosOfMember {
+                member,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_canonical_name(
+        &self,
+        app: &mut App,
+        name: String,
+    ) -> StdResult<CanonicalNameResponse> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::CanonicalName { name })
+    }
+
+    #[track_caller]
+    pub fn query_attestation(
+        &self,
+        app: &mut App,
+        subject: String,
+        issuer: String,
+        kind: String,
+    ) -> StdResult<AttestationResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Attestation {
+                subject,
+                issuer,
+                kind,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_attestations_by_subject(
+        &self,
+        app: &mut App,
+        subject: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<AttestationsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::AttestationsBySubject {
+                subject,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_attestations_by_issuer(
+        &self,
+        app: &mut App,
+        issuer: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> StdResult<AttestationsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::AttestationsByIssuer {
+                issuer,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_attestation_issuers(
+        &self,
+        app: &mut App,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AttestationIssuersResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::AttestationIssuers { start_after, limit },
+        )
+    }
 }
 
 impl From<IdentityserviceContract> for Addr {
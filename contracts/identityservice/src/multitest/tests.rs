@@ -1,20 +1,129 @@
 #![cfg(test)]
 
-use cosmwasm_std::{Addr, Decimal};
+use cosmwasm_std::{
+    coins, Addr, Binary, Coin, Decimal, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdResult,
+};
 use cw4::Member;
-use cw_multi_test::App;
-use cw_utils::Duration;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor as _};
+use cw_storage_plus::{Index, IndexList, IndexedMap, UniqueIndex};
+use cw_utils::{Duration, Threshold};
 use dao_members::multitest::contract::DaoMembersContract;
 use dao_multisig::multitest::contract::DaoMultisigContract;
+use dao_multisig::state::Executor;
 use serde::de::IntoDeserializer;
 
 use crate::{
-    msg::{DaosResponse, GetIdentityByNameResponse, GetIdentityByOwnerResponse},
-    state::Identity,
+    msg::{
+        DaosResponse, ExecuteMsg, GetIdentityByNameResponse, GetIdentityByOwnerResponse,
+        InstantiateMsg, OwnerIdentity, PendingIdentityTransferResponse, RegisterDaoMsg,
+        UpdateConfigMsg,
+    },
+    state::{IdType, Identity, IdentityStatus, NameFee, Profile, Social, DAOS, DAO_IDS},
+    upgrades, ContractError,
 };
 
 use super::contract::IdentityserviceContract;
 
+// Identity indexes of 0.1.0, which had no id type index
+struct LegacyIdentityIndexes<'a> {
+    owner: UniqueIndex<'a, String, Identity, String>,
+    name: UniqueIndex<'a, String, Identity, String>,
+}
+
+impl<'a> IndexList<Identity> for LegacyIdentityIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Identity>> + '_> {
+        let v: Vec<&dyn Index<Identity>> = vec![&self.owner, &self.name];
+        Box::new(v.into_iter())
+    }
+}
+
+// Registers users with the 0.1.0 storage layout, where both identity indexes shared the
+// namespace of the identities
+fn legacy_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let legacy_identities: IndexedMap<String, Identity, LegacyIdentityIndexes> = IndexedMap::new(
+        "identity",
+        LegacyIdentityIndexes {
+            owner: UniqueIndex::new(|d| d.owner.to_string(), "identity"),
+            name: UniqueIndex::new(|d| d.name.clone(), "identity"),
+        },
+    );
+
+    match msg {
+        ExecuteMsg::RegisterUser { name } => {
+            let identity = Identity {
+                owner: info.sender,
+                name,
+                id_type: IdType::User,
+                profile: None,
+                expires: None,
+                status: IdentityStatus::Active,
+            };
+            legacy_identities.save(deps.storage, identity.owner.to_string(), &identity)?;
+            Ok(Response::new())
+        }
+        _ => unimplemented!(),
+    }
+}
+
+fn legacy_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let response = crate::contract::instantiate(deps.branch(), env, info, msg)?;
+    cw2::set_contract_version(deps.storage, crate::contract::CONTRACT_NAME, "0.1.0")?;
+    Ok(response)
+}
+
+fn store_legacy_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(legacy_execute, legacy_instantiate, crate::contract::query);
+    app.store_code(Box::new(contract))
+}
+
+// Registers DAOs like 0.2.0, which had no reverse lookup from the DAO address to its id
+fn legacy_0_2_reply(mut deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let response = crate::contract::reply(deps.branch(), env, msg)?;
+    let daos = DAOS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, dao) in daos {
+        DAO_IDS.remove(deps.storage, &dao);
+    }
+    Ok(response)
+}
+
+fn legacy_0_2_instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let response = crate::contract::instantiate(deps.branch(), env, info, msg)?;
+    cw2::set_contract_version(
+        deps.storage,
+        crate::contract::CONTRACT_NAME,
+        upgrades::v0_3::FROM_VERSION,
+    )?;
+    Ok(response)
+}
+
+fn store_legacy_0_2_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        legacy_0_2_instantiate,
+        crate::contract::query,
+    )
+    .with_reply(legacy_0_2_reply);
+    app.store_code(Box::new(contract))
+}
+
 #[test]
 fn register_user() {
     let _owner = Addr::unchecked("owner");
@@ -51,7 +160,10 @@ fn register_user() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -64,7 +176,10 @@ fn register_user() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -105,7 +220,10 @@ fn register_user_with_same_name_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -118,7 +236,10 @@ fn register_user_with_same_name_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -171,7 +292,10 @@ fn register_user_with_same_wallet_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -184,7 +308,10 @@ fn register_user_with_same_wallet_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -257,7 +384,10 @@ fn register_dao() {
             identity: Some(Identity {
                 owner: Addr::unchecked("contract2").into(),
                 name: "my_dao".to_string(),
-                id_type: crate::state::IdType::Dao
+                id_type: crate::state::IdType::Dao,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -270,7 +400,10 @@ fn register_dao() {
             identity: Some(Identity {
                 owner: Addr::unchecked("contract2").into(),
                 name: "my_dao".to_string(),
-                id_type: crate::state::IdType::Dao
+                id_type: crate::state::IdType::Dao,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -336,7 +469,10 @@ fn register_dao_with_existing_dao_name_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("contract2").into(),
                 name: "my_dao".to_string(),
-                id_type: crate::state::IdType::Dao
+                id_type: crate::state::IdType::Dao,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -349,7 +485,10 @@ fn register_dao_with_existing_dao_name_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("contract2").into(),
                 name: "my_dao".to_string(),
-                id_type: crate::state::IdType::Dao
+                id_type: crate::state::IdType::Dao,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -418,7 +557,10 @@ fn register_dao_with_existing_user_name_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -431,7 +573,10 @@ fn register_dao_with_existing_user_name_fails() {
             identity: Some(Identity {
                 owner: Addr::unchecked("user1").into(),
                 name: "user1_name".to_string(),
-                id_type: crate::state::IdType::User
+                id_type: crate::state::IdType::User,
+                profile: None,
+                expires: None,
+                status: crate::state::IdentityStatus::Active
             })
         })
     );
@@ -465,3 +610,1667 @@ fn register_dao_with_existing_user_name_fails() {
         }
     );
 }
+#[test]
+fn transfer_identity() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let user3 = Addr::unchecked("user3");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    identityservice_contract
+        .register_user(&mut app, &user1, "alice".into())
+        .unwrap();
+
+    // Only the owner of an identity can offer it
+    let err = identityservice_contract
+        .transfer_identity(&mut app, &user2, user3.to_string())
+        .unwrap_err();
+    assert_eq!(err, crate::error::ContractError::NoIdentity {});
+
+    identityservice_contract
+        .transfer_identity(&mut app, &user1, user2.to_string())
+        .unwrap();
+    assert_eq!(
+        identityservice_contract
+            .query_pending_identity_transfer(&mut app, user1.to_string())
+            .unwrap(),
+        PendingIdentityTransferResponse {
+            to: Some(user2.clone())
+        }
+    );
+
+    // Only the recipient can accept the transfer
+    let err = identityservice_contract
+        .accept_identity_transfer(&mut app, &user3, user1.to_string())
+        .unwrap_err();
+    assert_eq!(err, crate::error::ContractError::Unauthorized {});
+
+    let response = identityservice_contract
+        .accept_identity_transfer(&mut app, &user2, user1.to_string())
+        .unwrap();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-identity_transferred"));
+
+    let identity_by_name_response = identityservice_contract
+        .query_get_identity_by_name(&mut app, "alice".to_string())
+        .unwrap();
+    assert_eq!(identity_by_name_response.identity.unwrap().owner, user2);
+
+    let identity_by_owner_response = identityservice_contract
+        .query_get_identity_by_owner(&mut app, user1.to_string())
+        .unwrap();
+    assert_eq!(identity_by_owner_response.identity, None);
+
+    // The old owner is free to register a new name
+    identityservice_contract
+        .register_user(&mut app, &user1, "bob".into())
+        .unwrap();
+
+    // Pending transfers can be cancelled
+    identityservice_contract
+        .transfer_identity(&mut app, &user2, user3.to_string())
+        .unwrap();
+    identityservice_contract
+        .cancel_identity_transfer(&mut app, &user2)
+        .unwrap();
+    let err = identityservice_contract
+        .accept_identity_transfer(&mut app, &user3, user2.to_string())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        crate::error::ContractError::NoPendingTransfer {
+            from: user2.to_string()
+        }
+    );
+}
+#[test]
+fn update_profile() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    identityservice_contract
+        .register_user(&mut app, &user1, "alice".into())
+        .unwrap();
+
+    let profile = Profile {
+        display_name: Some("Alice".into()),
+        avatar_uri: Some(
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".into(),
+        ),
+        website: Some("https://alice.example".into()),
+        socials: vec![Social {
+            platform: "github".into(),
+            handle: "alice".into(),
+        }],
+        bio: Some("Artist".into()),
+    };
+
+    // Users without an identity have no profile to update
+    let err = identityservice_contract
+        .update_profile(&mut app, &user2, Some(profile.clone()))
+        .unwrap_err();
+    assert_eq!(err, crate::error::ContractError::NoIdentity {});
+
+    identityservice_contract
+        .update_profile(&mut app, &user1, Some(profile.clone()))
+        .unwrap();
+
+    let identity_by_name_response = identityservice_contract
+        .query_get_identity_by_name(&mut app, "alice".to_string())
+        .unwrap();
+    assert_eq!(
+        identity_by_name_response.identity.unwrap().profile,
+        Some(profile.clone())
+    );
+
+    // Fields are checked against size limits
+    let err = identityservice_contract
+        .update_profile(
+            &mut app,
+            &user1,
+            Some(Profile {
+                bio: Some("a".repeat(513)),
+                ..profile
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        crate::error::ContractError::ProfileFieldTooLong {
+            field: "bio".into(),
+            length: 513,
+            max_length: 512
+        }
+    );
+
+    // The profile can be removed again
+    identityservice_contract
+        .update_profile(&mut app, &user1, None)
+        .unwrap();
+    let identity_by_owner_response = identityservice_contract
+        .query_get_identity_by_owner(&mut app, user1.to_string())
+        .unwrap();
+    assert_eq!(identity_by_owner_response.identity.unwrap().profile, None);
+}
+#[test]
+fn name_fees_and_expiry() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let user3 = Addr::unchecked("user3");
+    let governance = Addr::unchecked("governance");
+
+    let mut app = AppBuilder::new().build(|router, _, storage| {
+        for user in [&user1, &user2, &user3] {
+            router
+                .bank
+                .init_balance(storage, user, coins(1000, "ujmes"))
+                .unwrap();
+        }
+    });
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate_with_msg(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        &InstantiateMsg {
+            owner: governance.clone(),
+            dao_members_code_id,
+            dao_multisig_code_id,
+            governance_addr: governance.clone(),
+            name_fees: vec![
+                NameFee {
+                    min_length: 3,
+                    amount: Coin::new(100, "ujmes"),
+                },
+                NameFee {
+                    min_length: 6,
+                    amount: Coin::new(10, "ujmes"),
+                },
+            ],
+            name_registration_period: Some(1000),
+            name_grace_period: 100,
+            dao_fee: None,
+            dao_fee_recipient: None,
+            dao_fee_refund_period: None,
+            dao_registration_cooldown: 0,
+        },
+    )
+    .unwrap();
+
+    // Short names cost more
+    let err = identityservice_contract
+        .register_user_with_funds(&mut app, &user1, "abc".into(), &coins(50, "ujmes"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        crate::error::ContractError::InsufficientFee {
+            required: "100ujmes".into()
+        }
+    );
+
+    let start = app.block_info().time.seconds();
+    identityservice_contract
+        .register_user_with_funds(&mut app, &user1, "abc".into(), &coins(100, "ujmes"))
+        .unwrap();
+    identityservice_contract
+        .register_user_with_funds(&mut app, &user2, "abcdef".into(), &coins(10, "ujmes"))
+        .unwrap();
+
+    let governance_balance = app.wrap().query_balance(&governance, "ujmes").unwrap();
+    assert_eq!(governance_balance.amount.u128(), 110);
+
+    let identity = identityservice_contract
+        .query_get_identity_by_owner(&mut app, user1.to_string())
+        .unwrap()
+        .identity
+        .unwrap();
+    assert_eq!(identity.expires, Some(start + 1000));
+
+    // Expired names stay reserved for their owner during the grace period
+    app.update_block(|block| block.time = block.time.plus_seconds(1050));
+    let err = identityservice_contract
+        .register_user_with_funds(&mut app, &user3, "abc".into(), &coins(100, "ujmes"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        crate::error::ContractError::NameTaken { name: "abc".into() }
+    );
+
+    identityservice_contract
+        .renew_name(&mut app, &user1, &coins(100, "ujmes"))
+        .unwrap();
+    let identity = identityservice_contract
+        .query_get_identity_by_owner(&mut app, user1.to_string())
+        .unwrap()
+        .identity
+        .unwrap();
+    assert_eq!(identity.expires, Some(start + 2050));
+
+    // Once the grace period is over the name is released
+    app.update_block(|block| block.time = block.time.plus_seconds(1200));
+    let identity_by_name_response = identityservice_contract
+        .query_get_identity_by_name(&mut app, "abc".to_string())
+        .unwrap();
+    assert_eq!(identity_by_name_response.identity, None);
+
+    identityservice_contract
+        .register_user_with_funds(&mut app, &user3, "abc".into(), &coins(100, "ujmes"))
+        .unwrap();
+    let identity_by_name_response = identityservice_contract
+        .query_get_identity_by_name(&mut app, "abc".to_string())
+        .unwrap();
+    assert_eq!(identity_by_name_response.identity.unwrap().owner, user3);
+
+    let identity_by_owner_response = identityservice_contract
+        .query_get_identity_by_owner(&mut app, user1.to_string())
+        .unwrap();
+    assert_eq!(identity_by_owner_response.identity, None);
+}
+#[test]
+fn update_name_config() {
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let governance = Addr::unchecked("governance");
+
+    let mut app = AppBuilder::new().build(|router, _, storage| {
+        for user in [&user1, &user2] {
+            router
+                .bank
+                .init_balance(storage, user, coins(1000, "ujmes"))
+                .unwrap();
+        }
+    });
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate_with_msg(
+        &mut app,
+        identityservice_code_id,
+        &owner,
+        "identityservice",
+        &InstantiateMsg {
+            owner: owner.clone(),
+            dao_members_code_id,
+            dao_multisig_code_id,
+            governance_addr: governance.clone(),
+            name_fees: vec![],
+            name_registration_period: None,
+            name_grace_period: 0,
+            dao_fee: None,
+            dao_fee_recipient: None,
+            dao_fee_refund_period: None,
+            dao_registration_cooldown: 0,
+        },
+    )
+    .unwrap();
+
+    let name_config = UpdateConfigMsg {
+        name_fees: Some(vec![NameFee {
+            min_length: 3,
+            amount: Coin::new(50, "ujmes"),
+        }]),
+        name_registration_period: Some(1000),
+        name_grace_period: Some(100),
+        ..UpdateConfigMsg::default()
+    };
+
+    // Only the owner or governance can update the name fees and periods
+    let err = identityservice_contract
+        .update_config_with_msg(&mut app, &user1, name_config.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    identityservice_contract
+        .update_config_with_msg(&mut app, &governance, name_config)
+        .unwrap();
+
+    let err = identityservice_contract
+        .register_user(&mut app, &user1, "abc".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientFee {
+            required: "50ujmes".into()
+        }
+    );
+
+    let start = app.block_info().time.seconds();
+    identityservice_contract
+        .register_user_with_funds(&mut app, &user1, "abc".into(), &coins(50, "ujmes"))
+        .unwrap();
+    let identity = identityservice_contract
+        .query_get_identity_by_owner(&mut app, user1.to_string())
+        .unwrap()
+        .identity
+        .unwrap();
+    assert_eq!(identity.expires, Some(start + 1000));
+
+    // Without a grace period expired names are released right away
+    identityservice_contract
+        .update_config_with_msg(
+            &mut app,
+            &owner,
+            UpdateConfigMsg {
+                name_fees: Some(vec![]),
+                name_grace_period: Some(0),
+                ..UpdateConfigMsg::default()
+            },
+        )
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(1001));
+    identityservice_contract
+        .register_user(&mut app, &user2, "abc".into())
+        .unwrap();
+    let identity = identityservice_contract
+        .query_get_identity_by_name(&mut app, "abc".to_string())
+        .unwrap()
+        .identity
+        .unwrap();
+    assert_eq!(identity.owner, user2);
+    assert_eq!(identity.expires, Some(start + 1001 + 1000));
+}
+
+#[test]
+fn list_identities() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let user3 = Addr::unchecked("user3");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    identityservice_contract
+        .register_user(&mut app, &user1, "alice".into())
+        .unwrap();
+    identityservice_contract
+        .register_user(&mut app, &user2, "alfred".into())
+        .unwrap();
+    identityservice_contract
+        .register_user(&mut app, &user3, "bob".into())
+        .unwrap();
+    identityservice_contract
+        .register_dao(
+            &mut app,
+            &user1,
+            vec![
+                Member {
+                    addr: user1.to_string(),
+                    weight: 26,
+                },
+                Member {
+                    addr: user2.to_string(),
+                    weight: 26,
+                },
+            ],
+            "alpha".to_string(),
+            51u64,
+            Duration::Time(300),
+        )
+        .unwrap();
+
+    let names = |identities: Vec<Identity>| -> Vec<String> {
+        identities
+            .into_iter()
+            .map(|identity| identity.name)
+            .collect()
+    };
+
+    // Identities are listed by owner address
+    let identities = identityservice_contract
+        .query_identities(&mut app, None, Some(2), Some(IdType::User))
+        .unwrap()
+        .identities;
+    assert_eq!(names(identities), vec!["alice", "alfred"]);
+
+    let identities = identityservice_contract
+        .query_identities(&mut app, Some(user2.to_string()), None, Some(IdType::User))
+        .unwrap()
+        .identities;
+    assert_eq!(names(identities), vec!["bob"]);
+
+    let identities = identityservice_contract
+        .query_identities(&mut app, None, None, Some(IdType::Dao))
+        .unwrap()
+        .identities;
+    assert_eq!(names(identities), vec!["alpha"]);
+
+    let identities = identityservice_contract
+        .query_identities(&mut app, None, None, None)
+        .unwrap()
+        .identities;
+    assert_eq!(identities.len(), 4);
+
+    // Prefix search is ordered by name
+    let identities = identityservice_contract
+        .query_names_starting_with(&mut app, "al".into(), None, None)
+        .unwrap()
+        .identities;
+    assert_eq!(names(identities), vec!["alfred", "alice", "alpha"]);
+
+    let identities = identityservice_contract
+        .query_names_starting_with(&mut app, "al".into(), Some("alfred".into()), Some(1))
+        .unwrap()
+        .identities;
+    assert_eq!(names(identities), vec!["alice"]);
+
+    // Batch lookups keep the requested order and include owners without an identity
+    let identities = identityservice_contract
+        .query_identities_by_owners(&mut app, vec![user3.to_string(), "user4".to_string()])
+        .unwrap()
+        .identities;
+    assert_eq!(
+        identities,
+        vec![
+            OwnerIdentity {
+                owner: user3.to_string(),
+                identity: Some(Identity {
+                    owner: user3.clone(),
+                    name: "bob".to_string(),
+                    id_type: IdType::User,
+                    profile: None,
+                    expires: None,
+                    status: crate::state::IdentityStatus::Active,
+                }),
+            },
+            OwnerIdentity {
+                owner: "user4".to_string(),
+                identity: None,
+            },
+        ]
+    );
+}
+#[test]
+fn migrate_from_0_1_0_moves_identity_indexes() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let user3 = Addr::unchecked("user3");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let legacy_code_id = store_legacy_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        legacy_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    identityservice_contract
+        .register_user(&mut app, &user1, "alice".into())
+        .unwrap();
+    identityservice_contract
+        .register_user(&mut app, &user2, "bob".into())
+        .unwrap();
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    identityservice_contract
+        .migrate(&mut app, &user1, identityservice_code_id)
+        .unwrap();
+
+    // Only the identities themselves are left in the primary namespace
+    let identities = identityservice_contract
+        .query_identities(&mut app, None, None, None)
+        .unwrap()
+        .identities;
+    assert_eq!(identities.len(), 2);
+    assert_eq!(identities[0].name, "alice");
+    assert_eq!(identities[1].name, "bob");
+
+    // Existing identities are indexed by their type
+    let users = identityservice_contract
+        .query_identities(&mut app, None, None, Some(IdType::User))
+        .unwrap()
+        .identities;
+    assert_eq!(users, identities);
+
+    let identity_by_name_response = identityservice_contract
+        .query_get_identity_by_name(&mut app, "bob".to_string())
+        .unwrap();
+    assert_eq!(identity_by_name_response.identity.unwrap().owner, user2);
+
+    // Migrated names are still unique
+    let err = identityservice_contract
+        .register_user(&mut app, &user3, "alice".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NameTaken {
+            name: "alice".into()
+        }
+    );
+
+    identityservice_contract
+        .register_user(&mut app, &user3, "carol".into())
+        .unwrap();
+    let identities = identityservice_contract
+        .query_names_starting_with(&mut app, "".into(), None, None)
+        .unwrap()
+        .identities;
+    assert_eq!(identities.len(), 3);
+}
+#[test]
+fn migrate_from_0_2_0_indexes_dao_addresses() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let legacy_code_id = store_legacy_0_2_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        legacy_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    let members = vec![
+        Member {
+            addr: user1.to_string(),
+            weight: 26,
+        },
+        Member {
+            addr: user2.to_string(),
+            weight: 26,
+        },
+    ];
+    identityservice_contract
+        .register_dao(
+            &mut app,
+            &user1,
+            members,
+            "my_dao".to_string(),
+            51u64,
+            Duration::Time(300),
+        )
+        .unwrap();
+    let dao = Addr::unchecked("contract2");
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    identityservice_contract
+        .migrate(&mut app, &user1, identityservice_code_id)
+        .unwrap();
+
+    // The DAO registered before the migration is found by its address when it dissolves
+    identityservice_contract
+        .dissolve_dao(&mut app, &dao)
+        .unwrap();
+    let daos_response = identityservice_contract
+        .query_daos(&mut app, None, None, None)
+        .unwrap();
+    assert!(daos_response.daos.is_empty());
+}
+#[test]
+fn names_with_separators() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    // Separators can't pad or stretch a name
+    let err = identityservice_contract
+        .register_user(&mut app, &user1, "_foo".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SeparatorAtEdge {
+            name: "_foo".into()
+        }
+    );
+
+    let err = identityservice_contract
+        .register_user(&mut app, &user1, "foo.".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SeparatorAtEdge {
+            name: "foo.".into()
+        }
+    );
+
+    let err = identityservice_contract
+        .register_user(&mut app, &user1, "foo.-bar".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ConsecutiveSeparators {
+            name: "foo.-bar".into()
+        }
+    );
+
+    identityservice_contract
+        .register_user(&mut app, &user1, "foo-bar".into())
+        .unwrap();
+
+    // Names that only differ in their separators collide
+    let err = identityservice_contract
+        .register_user(&mut app, &user2, "foo_bar".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NameTaken {
+            name: "foo_bar".into()
+        }
+    );
+
+    let identity_by_name_response = identityservice_contract
+        .query_get_identity_by_name(&mut app, "foo.bar".to_string())
+        .unwrap();
+    assert_eq!(identity_by_name_response.identity.unwrap().name, "foo-bar");
+
+    let canonical_name_response = identityservice_contract
+        .query_canonical_name(&mut app, "foo.bar_baz".to_string())
+        .unwrap();
+    assert_eq!(canonical_name_response.canonical_name, "foo-bar-baz");
+
+    identityservice_contract
+        .query_canonical_name(&mut app, "foo__bar".to_string())
+        .unwrap_err();
+}
+#[test]
+fn dissolve_dao() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    let members = vec![
+        Member {
+            addr: user1.to_string(),
+            weight: 26,
+        },
+        Member {
+            addr: user2.to_string(),
+            weight: 26,
+        },
+    ];
+    identityservice_contract
+        .register_dao(
+            &mut app,
+            &user1,
+            members,
+            "my_dao".to_string(),
+            51u64,
+            Duration::Time(300),
+        )
+        .unwrap();
+    let dao = Addr::unchecked("contract2");
+
+    // Only the DAO multisig can dissolve the DAO
+    identityservice_contract
+        .register_user(&mut app, &user1, "user1_name".into())
+        .unwrap();
+    let err = identityservice_contract
+        .dissolve_dao(&mut app, &user1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let response = identityservice_contract
+        .dissolve_dao(&mut app, &dao)
+        .unwrap();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-dao_dissolved"));
+
+    let daos_response = identityservice_contract
+        .query_daos(&mut app, None, None, None)
+        .unwrap();
+    assert!(daos_response.daos.is_empty());
+
+    // The identity stays as a tombstone and keeps the name reserved
+    let identity = identityservice_contract
+        .query_get_identity_by_owner(&mut app, dao.to_string())
+        .unwrap()
+        .identity
+        .unwrap();
+    assert_eq!(identity.status, IdentityStatus::Dissolved);
+
+    let err = identityservice_contract
+        .dissolve_dao(&mut app, &dao)
+        .unwrap_err();
+    assert_eq!(err, ContractError::DaoDissolved {});
+
+    let err = identityservice_contract
+        .register_user(&mut app, &user2, "my-dao".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NameTaken {
+            name: "my-dao".into()
+        }
+    );
+}
+#[test]
+fn register_dao_with_custom_settings() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    identityservice_contract
+        .register_dao_with_msg(
+            &mut app,
+            &user1,
+            &[],
+            RegisterDaoMsg {
+                members: vec![
+                    Member {
+                        addr: user1.to_string(),
+                        weight: 26,
+                    },
+                    Member {
+                        addr: user2.to_string(),
+                        weight: 26,
+                    },
+                ],
+                dao_name: "my_dao".to_string(),
+                threshold_percentage: 51,
+                max_voting_period: Duration::Time(300),
+                executor: Some(Executor::Only(user1.clone())),
+                multisig_threshold: Some(Threshold::AbsolutePercentage {
+                    percentage: Decimal::percent(75),
+                }),
+                admin: Some(user1.to_string()),
+            },
+        )
+        .unwrap();
+
+    let dao_members = Addr::unchecked("contract1");
+    let dao = Addr::unchecked("contract2");
+
+    let dao_multisig_config = dao_multisig::state::CONFIG
+        .query(&app.wrap(), dao.clone())
+        .unwrap();
+    assert_eq!(
+        dao_multisig_config.executor,
+        Some(Executor::Only(user1.clone()))
+    );
+    assert_eq!(
+        dao_multisig_config.threshold,
+        Threshold::AbsolutePercentage {
+            percentage: Decimal::percent(75)
+        }
+    );
+
+    // Both contracts can be migrated by the admin
+    assert_eq!(
+        app.contract_data(&dao_members).unwrap().admin,
+        Some(user1.clone())
+    );
+    assert_eq!(app.contract_data(&dao).unwrap().admin, Some(user1));
+}
+#[test]
+fn dao_registration_fee() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let governance = Addr::unchecked("governance");
+    let treasury = Addr::unchecked("treasury");
+
+    let mut app = AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &user1, coins(2000, "ujmes"))
+            .unwrap();
+    });
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate_with_msg(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        &InstantiateMsg {
+            owner: governance.clone(),
+            dao_members_code_id,
+            dao_multisig_code_id,
+            governance_addr: governance.clone(),
+            name_fees: vec![],
+            name_registration_period: None,
+            name_grace_period: 0,
+            dao_fee: Some(Coin::new(500, "ujmes")),
+            dao_fee_recipient: Some(treasury.clone()),
+            dao_fee_refund_period: Some(1000),
+            dao_registration_cooldown: 100,
+        },
+    )
+    .unwrap();
+
+    let register_dao_msg = |dao_name: &str| RegisterDaoMsg {
+        members: vec![
+            Member {
+                addr: user1.to_string(),
+                weight: 26,
+            },
+            Member {
+                addr: user2.to_string(),
+                weight: 26,
+            },
+        ],
+        dao_name: dao_name.to_string(),
+        threshold_percentage: 51,
+        max_voting_period: Duration::Time(300),
+        executor: None,
+        multisig_threshold: None,
+        admin: None,
+    };
+
+    let err = identityservice_contract
+        .register_dao_with_msg(&mut app, &user1, &[], register_dao_msg("my_dao"))
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Payment(_)));
+
+    identityservice_contract
+        .register_dao_with_msg(
+            &mut app,
+            &user1,
+            &coins(500, "ujmes"),
+            register_dao_msg("my_dao"),
+        )
+        .unwrap();
+    let dao = Addr::unchecked("contract2");
+
+    let dao_fee = identityservice_contract
+        .query_dao_fee(&mut app, dao.to_string())
+        .unwrap()
+        .fee
+        .unwrap();
+    assert_eq!(dao_fee.payer, user1);
+    assert_eq!(dao_fee.amount, Coin::new(500, "ujmes"));
+
+    // Registrations of the same address are rate limited
+    let err = identityservice_contract
+        .register_dao_with_msg(
+            &mut app,
+            &user1,
+            &coins(500, "ujmes"),
+            register_dao_msg("other_dao"),
+        )
+        .unwrap_err();
+    assert!(matches!(err, ContractError::DaoRegistrationCooldown { .. }));
+
+    // Only governance refunds the fee, once the DAO had a successful proposal
+    let err = identityservice_contract
+        .refund_dao_fee(&mut app, &user1, dao.to_string())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    identityservice_contract
+        .refund_dao_fee(&mut app, &governance, dao.to_string())
+        .unwrap();
+    let balance = app.wrap().query_balance(&user1, "ujmes").unwrap();
+    assert_eq!(balance.amount.u128(), 2000);
+
+    // Later successful proposals don't refund anything
+    identityservice_contract
+        .refund_dao_fee(&mut app, &governance, dao.to_string())
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    identityservice_contract
+        .register_dao_with_msg(
+            &mut app,
+            &user1,
+            &coins(500, "ujmes"),
+            register_dao_msg("other_dao"),
+        )
+        .unwrap();
+    let other_dao = Addr::unchecked("contract4");
+
+    // Fees that are not refunded in time go to the fee recipient
+    let err = identityservice_contract
+        .sweep_dao_fee(&mut app, &user2, other_dao.to_string())
+        .unwrap_err();
+    assert_eq!(err, ContractError::DaoFeeRefundable {});
+
+    app.update_block(|block| block.time = block.time.plus_seconds(1000));
+    identityservice_contract
+        .sweep_dao_fee(&mut app, &user2, other_dao.to_string())
+        .unwrap();
+    let balance = app.wrap().query_balance(&treasury, "ujmes").unwrap();
+    assert_eq!(balance.amount.u128(), 500);
+    assert_eq!(
+        identityservice_contract
+            .query_dao_fee(&mut app, other_dao.to_string())
+            .unwrap()
+            .fee,
+        None
+    );
+}
+
+#[test]
+fn update_dao_fee_config() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let governance = Addr::unchecked("governance");
+    let treasury = Addr::unchecked("treasury");
+
+    let mut app = AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &user1, coins(2000, "ujmes"))
+            .unwrap();
+    });
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        governance.clone(),
+    )
+    .unwrap();
+
+    let register_dao_msg = |dao_name: &str| RegisterDaoMsg {
+        members: vec![
+            Member {
+                addr: user1.to_string(),
+                weight: 26,
+            },
+            Member {
+                addr: user2.to_string(),
+                weight: 26,
+            },
+        ],
+        dao_name: dao_name.to_string(),
+        threshold_percentage: 51,
+        max_voting_period: Duration::Time(300),
+        executor: None,
+        multisig_threshold: None,
+        admin: None,
+    };
+
+    let dao_fee_config = UpdateConfigMsg {
+        dao_fee: Some(Coin::new(500, "ujmes")),
+        dao_fee_recipient: Some(treasury.to_string()),
+        dao_fee_refund_period: Some(1000),
+        dao_registration_cooldown: Some(100),
+        ..UpdateConfigMsg::default()
+    };
+
+    // Only the owner or governance can update the DAO fee settings
+    let err = identityservice_contract
+        .update_config_with_msg(&mut app, &user1, dao_fee_config.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    identityservice_contract
+        .update_config_with_msg(&mut app, &governance, dao_fee_config)
+        .unwrap();
+
+    let err = identityservice_contract
+        .register_dao_with_msg(&mut app, &user1, &[], register_dao_msg("my_dao"))
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Payment(_)));
+
+    identityservice_contract
+        .register_dao_with_msg(
+            &mut app,
+            &user1,
+            &coins(500, "ujmes"),
+            register_dao_msg("my_dao"),
+        )
+        .unwrap();
+    let dao = Addr::unchecked("contract2");
+
+    let err = identityservice_contract
+        .register_dao_with_msg(
+            &mut app,
+            &user1,
+            &coins(500, "ujmes"),
+            register_dao_msg("other_dao"),
+        )
+        .unwrap_err();
+    assert!(matches!(err, ContractError::DaoRegistrationCooldown { .. }));
+
+    // A shorter refund period applies to the fees already held
+    identityservice_contract
+        .update_config_with_msg(
+            &mut app,
+            &governance,
+            UpdateConfigMsg {
+                dao_fee: Some(Coin::new(0, "ujmes")),
+                dao_fee_refund_period: Some(10),
+                dao_registration_cooldown: Some(0),
+                ..UpdateConfigMsg::default()
+            },
+        )
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(10));
+    identityservice_contract
+        .sweep_dao_fee(&mut app, &user2, dao.to_string())
+        .unwrap();
+    let balance = app.wrap().query_balance(&treasury, "ujmes").unwrap();
+    assert_eq!(balance.amount.u128(), 500);
+
+    // Without a fee and cooldown DAOs are registered for free right away
+    identityservice_contract
+        .register_dao_with_msg(&mut app, &user1, &[], register_dao_msg("other_dao"))
+        .unwrap();
+    let other_dao = Addr::unchecked("contract4");
+    assert_eq!(
+        identityservice_contract
+            .query_dao_fee(&mut app, other_dao.to_string())
+            .unwrap()
+            .fee,
+        None
+    );
+}
+
+#[test]
+fn attestations() {
+    let owner = Addr::unchecked("owner");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let issuer = Addr::unchecked("issuer");
+    let governance = Addr::unchecked("governance");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &owner,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        governance.clone(),
+    )
+    .unwrap();
+
+    identityservice_contract
+        .register_user(&mut app, &user1, "user1_name".into())
+        .unwrap();
+
+    // Only governance and allow-listed issuers can attest
+    let err = identityservice_contract
+        .attest(
+            &mut app,
+            &issuer,
+            user1.to_string(),
+            "kyc".into(),
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = identityservice_contract
+        .update_attestation_issuers(&mut app, &user1, vec![issuer.to_string()], vec![])
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    identityservice_contract
+        .update_attestation_issuers(&mut app, &owner, vec![issuer.to_string()], vec![])
+        .unwrap();
+    let issuers = identityservice_contract
+        .query_attestation_issuers(&mut app, None, None)
+        .unwrap()
+        .issuers;
+    assert_eq!(issuers, vec![issuer.clone()]);
+
+    // The subject needs an identity
+    let err = identityservice_contract
+        .attest(
+            &mut app,
+            &issuer,
+            user2.to_string(),
+            "kyc".into(),
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NoIdentity {});
+
+    let err = identityservice_contract
+        .attest(&mut app, &issuer, user1.to_string(), "".into(), None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::EmptyAttestationKind {});
+
+    let now = app.block_info().time.seconds();
+    let err = identityservice_contract
+        .attest(
+            &mut app,
+            &issuer,
+            user1.to_string(),
+            "kyc".into(),
+            Some(now),
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::AttestationExpired { expires: now });
+
+    let response = identityservice_contract
+        .attest(
+            &mut app,
+            &issuer,
+            user1.to_string(),
+            "kyc".into(),
+            Some(now + 100),
+            Some(Binary::from(vec![1u8; 32])),
+        )
+        .unwrap();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-attested"));
+    identityservice_contract
+        .attest(
+            &mut app,
+            &governance,
+            user1.to_string(),
+            "member".into(),
+            None,
+            None,
+        )
+        .unwrap();
+
+    let attestation = identityservice_contract
+        .query_attestation(
+            &mut app,
+            user1.to_string(),
+            issuer.to_string(),
+            "kyc".into(),
+        )
+        .unwrap()
+        .attestation
+        .unwrap();
+    assert_eq!(attestation.issued_at, now);
+    assert_eq!(attestation.data_hash, Some(Binary::from(vec![1u8; 32])));
+
+    let attestations = identityservice_contract
+        .query_attestations_by_subject(&mut app, user1.to_string(), None, None)
+        .unwrap()
+        .attestations;
+    assert_eq!(attestations.len(), 2);
+
+    let attestations = identityservice_contract
+        .query_attestations_by_subject(
+            &mut app,
+            user1.to_string(),
+            Some((governance.to_string(), "member".into())),
+            None,
+        )
+        .unwrap()
+        .attestations;
+    assert_eq!(attestations.len(), 1);
+    assert_eq!(attestations[0].issuer, issuer);
+
+    let attestations = identityservice_contract
+        .query_attestations_by_issuer(&mut app, issuer.to_string(), None, None)
+        .unwrap()
+        .attestations;
+    assert_eq!(attestations.len(), 1);
+    assert_eq!(attestations[0].kind, "kyc");
+
+    // Expired attestations are hidden from the single lookup and the listings
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let response = identityservice_contract
+        .query_attestation(
+            &mut app,
+            user1.to_string(),
+            issuer.to_string(),
+            "kyc".into(),
+        )
+        .unwrap();
+    assert!(response.attestation.is_none());
+
+    let attestations = identityservice_contract
+        .query_attestations_by_subject(&mut app, user1.to_string(), None, None)
+        .unwrap()
+        .attestations;
+    assert_eq!(attestations.len(), 1);
+    assert_eq!(attestations[0].kind, "member");
+
+    let attestations = identityservice_contract
+        .query_attestations_by_issuer(&mut app, issuer.to_string(), None, None)
+        .unwrap()
+        .attestations;
+    assert!(attestations.is_empty());
+
+    // Only the issuer can revoke its attestation
+    let err = identityservice_contract
+        .revoke(&mut app, &issuer, user1.to_string(), "member".into())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NoAttestation {
+            subject: user1.to_string(),
+            kind: "member".into()
+        }
+    );
+    identityservice_contract
+        .revoke(&mut app, &governance, user1.to_string(), "member".into())
+        .unwrap();
+
+    let attestations = identityservice_contract
+        .query_attestations_by_issuer(&mut app, governance.to_string(), None, None)
+        .unwrap()
+        .attestations;
+    assert!(attestations.is_empty());
+
+    // Removed issuers cannot attest anymore
+    identityservice_contract
+        .update_attestation_issuers(&mut app, &governance, vec![], vec![issuer.to_string()])
+        .unwrap();
+    let err = identityservice_contract
+        .attest(
+            &mut app,
+            &issuer,
+            user1.to_string(),
+            "kyc".into(),
+            None,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn update_config_and_dao_templates() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let governance = Addr::unchecked("governance");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        governance.clone(),
+    )
+    .unwrap();
+
+    let members = vec![
+        Member {
+            addr: user1.to_string(),
+            weight: 26,
+        },
+        Member {
+            addr: user2.to_string(),
+            weight: 26,
+        },
+    ];
+    identityservice_contract
+        .register_dao(
+            &mut app,
+            &user1,
+            members.clone(),
+            "first_dao".to_string(),
+            51u64,
+            Duration::Time(300),
+        )
+        .unwrap();
+    let first_dao = Addr::unchecked("contract2");
+
+    // Only the owner or governance can update the config
+    let err = identityservice_contract
+        .update_config(&mut app, &user2, None, None, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Updating anything but the code ids keeps the template version
+    identityservice_contract
+        .update_config(
+            &mut app,
+            &user1,
+            Some(user2.to_string()),
+            None,
+            Some(dao_members_code_id),
+            None,
+        )
+        .unwrap();
+    let templates = identityservice_contract
+        .query_dao_templates(&mut app, None, None)
+        .unwrap()
+        .templates;
+    assert_eq!(templates.len(), 1);
+
+    // The previous owner lost access
+    let err = identityservice_contract
+        .update_config(&mut app, &user1, None, None, None, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let new_dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+    identityservice_contract
+        .update_config(
+            &mut app,
+            &governance,
+            None,
+            None,
+            None,
+            Some(new_dao_multisig_code_id),
+        )
+        .unwrap();
+
+    identityservice_contract
+        .register_dao(
+            &mut app,
+            &user1,
+            members,
+            "second_dao".to_string(),
+            51u64,
+            Duration::Time(300),
+        )
+        .unwrap();
+    let second_dao = Addr::unchecked("contract4");
+    assert_eq!(
+        app.contract_data(&second_dao).unwrap().code_id,
+        new_dao_multisig_code_id
+    );
+
+    let templates = identityservice_contract
+        .query_dao_templates(&mut app, None, None)
+        .unwrap()
+        .templates;
+    assert_eq!(templates.len(), 2);
+    assert_eq!(templates[1].version, 2);
+    assert_eq!(templates[1].dao_members_code_id, dao_members_code_id);
+    assert_eq!(templates[1].dao_multisig_code_id, new_dao_multisig_code_id);
+
+    let template = identityservice_contract
+        .query_dao_template(&mut app, first_dao.to_string())
+        .unwrap()
+        .template
+        .unwrap();
+    assert_eq!(template.version, 1);
+    assert_eq!(template.dao_multisig_code_id, dao_multisig_code_id);
+
+    let template = identityservice_contract
+        .query_dao_template(&mut app, second_dao.to_string())
+        .unwrap()
+        .template
+        .unwrap();
+    assert_eq!(template.version, 2);
+
+    let response = identityservice_contract
+        .query_dao_template(&mut app, user1.to_string())
+        .unwrap();
+    assert!(response.template.is_none());
+}
+
+#[test]
+fn daos_of_member() {
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let user3 = Addr::unchecked("user3");
+
+    let mut app = App::default();
+
+    let dao_members_code_id = DaoMembersContract::store_code(&mut app);
+    let dao_multisig_code_id = DaoMultisigContract::store_code(&mut app);
+
+    let identityservice_code_id = IdentityserviceContract::store_code(&mut app);
+    let identityservice_contract = IdentityserviceContract::instantiate(
+        &mut app,
+        identityservice_code_id,
+        &user1,
+        "identityservice",
+        dao_members_code_id,
+        dao_multisig_code_id,
+        Addr::unchecked("governance"),
+    )
+    .unwrap();
+
+    identityservice_contract
+        .register_dao(
+            &mut app,
+            &user1,
+            vec![
+                Member {
+                    addr: user1.to_string(),
+                    weight: 26,
+                },
+                Member {
+                    addr: user2.to_string(),
+                    weight: 26,
+                },
+            ],
+            "first_dao".to_string(),
+            51u64,
+            Duration::Time(300),
+        )
+        .unwrap();
+    let first_dao_members = Addr::unchecked("contract1");
+    let first_dao = Addr::unchecked("contract2");
+
+    identityservice_contract
+        .register_dao(
+            &mut app,
+            &user2,
+            vec![
+                Member {
+                    addr: user1.to_string(),
+                    weight: 26,
+                },
+                Member {
+                    addr: user3.to_string(),
+                    weight: 26,
+                },
+            ],
+            "second_dao".to_string(),
+            51u64,
+            Duration::Time(300),
+        )
+        .unwrap();
+    let second_dao = Addr::unchecked("contract4");
+
+    let daos = identityservice_contract
+        .query_daos_of_member(&mut app, user1.to_string(), None, None)
+        .unwrap()
+        .daos;
+    assert_eq!(daos, vec![first_dao.clone(), second_dao.clone()]);
+
+    let daos = identityservice_contract
+        .query_daos_of_member(
+            &mut app,
+            user1.to_string(),
+            Some(first_dao.to_string()),
+            None,
+        )
+        .unwrap()
+        .daos;
+    assert_eq!(daos, vec![second_dao.clone()]);
+
+    // Only the dao-members contracts of registered DAOs can call the hook
+    let err = app
+        .execute_contract(
+            user1.clone(),
+            identityservice_contract.addr().clone(),
+            &ExecuteMsg::MemberChangedHook(cw4::MemberChangedHookMsg { diffs: vec![] }),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // identityservice is registered as hook before handing the admin over to the DAO
+    let hooks: cw_controllers::HooksResponse = app
+        .wrap()
+        .query_wasm_smart(
+            first_dao_members.clone(),
+            &dao_members::msg::QueryMsg::Hooks {},
+        )
+        .unwrap();
+    assert_eq!(
+        hooks.hooks,
+        vec![identityservice_contract.addr().to_string()]
+    );
+
+    // Member updates of the DAO are tracked through the hook
+    app.execute_contract(
+        first_dao_members,
+        identityservice_contract.addr().clone(),
+        &ExecuteMsg::MemberChangedHook(cw4::MemberChangedHookMsg {
+            diffs: vec![
+                cw4::MemberDiff::new(user3.clone(), None, Some(26)),
+                cw4::MemberDiff::new(user2.clone(), Some(26), None),
+            ],
+        }),
+        &[],
+    )
+    .unwrap();
+
+    let daos = identityservice_contract
+        .query_daos_of_member(&mut app, user2.to_string(), None, None)
+        .unwrap()
+        .daos;
+    assert!(daos.is_empty());
+
+    let daos = identityservice_contract
+        .query_daos_of_member(&mut app, user3.to_string(), None, None)
+        .unwrap()
+        .daos;
+    assert_eq!(daos, vec![first_dao.clone(), second_dao.clone()]);
+
+    // Dissolved DAOs are no longer listed
+    identityservice_contract
+        .dissolve_dao(&mut app, &first_dao)
+        .unwrap();
+    let daos = identityservice_contract
+        .query_daos_of_member(&mut app, user3.to_string(), None, None)
+        .unwrap()
+        .daos;
+    assert_eq!(daos, vec![second_dao]);
+}
@@ -1,8 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, StdResult, Storage};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, UniqueIndex};
+use cosmwasm_std::{Addr, Binary, Coin, Empty, Env, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+use cw_utils::Threshold;
+use dao_multisig::state::Executor;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -10,14 +12,111 @@ pub struct Config {
     pub dao_members_code_id: u64,
     pub dao_multisig_code_id: u64,
     pub governance_addr: Addr,
+    // Fees for registering or renewing a user name, sent to the governance contract
+    #[serde(default)]
+    pub name_fees: Vec<NameFee>,
+    // Seconds a user name is registered for, names never expire if not set
+    #[serde(default)]
+    pub name_registration_period: Option<u64>,
+    // Seconds after expiry during which only the owner can renew the name
+    #[serde(default)]
+    pub name_grace_period: u64,
+    // Fee for registering a DAO, held until the DAO's first successful governance proposal
+    #[serde(default)]
+    pub dao_fee: Option<Coin>,
+    // Receives DAO fees that are not refunded, defaults to the governance contract
+    #[serde(default)]
+    pub dao_fee_recipient: Option<Addr>,
+    // Seconds a DAO has to get a proposal through before its fee can be swept to the recipient
+    #[serde(default)]
+    pub dao_fee_refund_period: Option<u64>,
+    // Seconds an address has to wait between two DAO registrations
+    #[serde(default)]
+    pub dao_registration_cooldown: u64,
+    // Version of the DAO template in DAO_TEMPLATES that matches the code ids above
+    #[serde(default)]
+    pub dao_template_version: u64,
+}
+
+impl Config {
+    pub fn dao_fee_recipient(&self) -> &Addr {
+        self.dao_fee_recipient
+            .as_ref()
+            .unwrap_or(&self.governance_addr)
+    }
+
+    /// Returns the fee of the tier with the largest `min_length` the name qualifies for
+    pub fn name_fee(&self, name: &str) -> Option<Coin> {
+        self.name_fees
+            .iter()
+            .filter(|fee| fee.min_length <= name.len() as u64)
+            .max_by_key(|fee| fee.min_length)
+            .map(|fee| fee.amount.clone())
+    }
+}
+
+// Shorter names are more sought after, so fees are priced by name length
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct NameFee {
+    pub min_length: u64,
+    pub amount: Coin,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+// Code ids new DAOs are instantiated with, a new version is added whenever a code id is updated
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoTemplate {
+    pub version: u64,
+    pub dao_members_code_id: u64,
+    pub dao_multisig_code_id: u64,
+    pub added_at: u64,
+}
+
+pub const DAO_TEMPLATES: Map<u64, DaoTemplate> = Map::new("dao_templates");
+// Template version each DAO (dao-multisig address) was created with
+pub const DAO_TEMPLATE_VERSIONS: Map<&Addr, u64> = Map::new("dao_template_versions");
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Identity {
     pub owner: Addr,
     pub name: String,
     pub id_type: IdType,
+    #[serde(default)]
+    pub profile: Option<Profile>,
+    // Epoch in seconds when the name expires, DAO names and names registered without expiry have none
+    #[serde(default)]
+    pub expires: Option<u64>,
+    #[serde(default)]
+    pub status: IdentityStatus,
+}
+
+impl Identity {
+    /// Released identities expired longer than the grace period ago, their name can be claimed again
+    pub fn is_released(&self, config: &Config, env: &Env) -> bool {
+        self.expires.map_or(false, |expires| {
+            env.block.time.seconds() > expires + config.name_grace_period
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct Profile {
+    pub display_name: Option<String>,
+    pub avatar_uri: Option<String>,
+    pub website: Option<String>,
+    pub socials: Vec<Social>,
+    pub bio: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Social {
+    // e.g. "twitter" or "github"
+    pub platform: String,
+    pub handle: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -27,29 +126,154 @@ pub enum IdType {
     Dao,
 }
 
+impl IdType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdType::User => "user",
+            IdType::Dao => "dao",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentityStatus {
+    Active,
+    // Dissolved DAOs keep their identity as a tombstone so the name can't be taken over
+    Dissolved,
+}
+
+impl Default for IdentityStatus {
+    fn default() -> Self {
+        IdentityStatus::Active
+    }
+}
+
 pub struct IdentityIndexes<'a> {
     // pk goes to second tuple element
     pub owner: UniqueIndex<'a, String, Identity, String>,
     pub name: UniqueIndex<'a, String, Identity, String>,
+    pub id_type: MultiIndex<'a, String, Identity, String>,
 }
 
 impl<'a> IndexList<Identity> for IdentityIndexes<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Identity>> + '_> {
-        let v: Vec<&dyn Index<Identity>> = vec![&self.owner, &self.name];
+        let v: Vec<&dyn Index<Identity>> = vec![&self.owner, &self.name, &self.id_type];
         Box::new(v.into_iter())
     }
 }
 
 pub fn identities<'a>() -> IndexedMap<'a, String, Identity, IdentityIndexes<'a>> {
     let indexes = IdentityIndexes {
-        owner: UniqueIndex::new(|d| d.owner.clone().to_string(), "identity"),
-        name: UniqueIndex::new(|d| d.name.clone().to_string(), "identity"),
+        owner: UniqueIndex::new(|d| d.owner.clone().to_string(), "identity__owner"),
+        name: UniqueIndex::new(|d| canonical_name(&d.name), "identity__name"),
+        id_type: MultiIndex::new(
+            |_pk, d| d.id_type.as_str().to_string(),
+            "identity",
+            "identity__id_type",
+        ),
     };
     IndexedMap::new("identity", indexes)
 }
 
+/// Names are unique by their canonical form, where every separator is replaced by `-`,
+/// so `foo-bar`, `foo_bar` and `foo.bar` can't be registered next to each other
+pub fn canonical_name(name: &str) -> String {
+    name.replace(|c| c == '.' || c == '_', "-")
+}
+
+// Transfers offered by the current owner (key) that the recipient (value) still has to accept
+pub const PENDING_TRANSFERS: Map<&Addr, Addr> = Map::new("pending_transfers");
+
+// Settings of the DAO being registered that are only needed once its dao-members contract
+// is instantiated, they are removed again when the dao-multisig is instantiated
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PendingDao {
+    pub executor: Executor,
+    pub multisig_threshold: Option<Threshold>,
+    pub admin: Option<Addr>,
+    pub payer: Addr,
+    pub fee: Option<Coin>,
+    #[serde(default)]
+    pub dao_template_version: u64,
+    // Initial members, dao-members doesn't call its hooks on instantiate
+    #[serde(default)]
+    pub members: Vec<Addr>,
+}
+
+pub const PENDING_DAO: Item<PendingDao> = Item::new("pending_dao");
+
+// Registration fee held for a DAO (key) until it is refunded to the payer or swept to the recipient
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DaoFee {
+    pub payer: Addr,
+    pub amount: Coin,
+    pub paid_at: u64,
+}
+
+pub const DAO_FEES: Map<&Addr, DaoFee> = Map::new("dao_fees");
+// Time of the last DAO registration of an address, used to enforce the registration cooldown
+pub const LAST_DAO_REGISTRATION: Map<&Addr, u64> = Map::new("last_dao_registration");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Attestation {
+    pub subject: Addr,
+    pub issuer: Addr,
+    // e.g. "kyc" or "core_contributor"
+    pub kind: String,
+    pub issued_at: u64,
+    // Epoch in seconds from which the attestation is no longer valid
+    pub expires: Option<u64>,
+    // Hash of off-chain data backing the attestation
+    pub data_hash: Option<Binary>,
+}
+
+impl Attestation {
+    pub fn is_expired(&self, env: &Env) -> bool {
+        self.expires
+            .map_or(false, |expires| env.block.time.seconds() >= expires)
+    }
+}
+
+pub struct AttestationIndexes<'a> {
+    pub issuer: MultiIndex<'a, Addr, Attestation, (Addr, Addr, String)>,
+}
+
+impl<'a> IndexList<Attestation> for AttestationIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Attestation>> + '_> {
+        let v: Vec<&dyn Index<Attestation>> = vec![&self.issuer];
+        Box::new(v.into_iter())
+    }
+}
+
+// Keyed by (subject, issuer, kind), an issuer can attest each kind once per subject
+pub fn attestations<'a>(
+) -> IndexedMap<'a, (Addr, Addr, String), Attestation, AttestationIndexes<'a>> {
+    let indexes = AttestationIndexes {
+        issuer: MultiIndex::new(
+            |_pk, d| d.issuer.clone(),
+            "attestations",
+            "attestations__issuer",
+        ),
+    };
+    IndexedMap::new("attestations", indexes)
+}
+
+// Addresses besides governance that are allowed to issue attestations
+pub const ATTESTATION_ISSUERS: Map<&Addr, Empty> = Map::new("attestation_issuers");
+
 pub const DAO_COUNT: Item<u64> = Item::new("dao_count");
 pub const DAOS: Map<u64, Addr> = Map::new("daos");
+// Reverse lookup of DAOS, filled in for older DAOs by the 0.3.0 migration
+pub const DAO_IDS: Map<&Addr, u64> = Map::new("dao_ids");
+
+// dao-members contract (key) of each DAO (value), used to authorize its member changed hooks
+pub const DAO_MEMBERS_CONTRACTS: Map<&Addr, Addr> = Map::new("dao_members_contracts");
+// DAOs (second key) a member (first key) is part of, only DAOs registered since 0.3.0 are tracked
+pub const DAO_MEMBERSHIPS: Map<(&Addr, &Addr), Empty> = Map::new("dao_memberships");
 
 pub fn next_dao_id(store: &mut dyn Storage) -> StdResult<u64> {
     let id: u64 = DAO_COUNT.may_load(store)?.unwrap_or_default() + 1;
@@ -0,0 +1,2 @@
+pub mod v0_2;
+pub mod v0_3;
@@ -0,0 +1,50 @@
+use cosmwasm_std::{Binary, DepsMut, Order, StdResult};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::CONTRACT_NAME;
+use crate::state::{identities, Identity};
+use crate::ContractError;
+
+// Last release with the identity indexes stored in the namespace of the identities
+pub const FROM_VERSION: &str = "0.1.0";
+pub const TO_VERSION: &str = "0.2.0";
+
+// Up to 0.1.0 the owner and name indexes shared the "identity" namespace with the identities.
+// The owner index entries were overwritten by the identities themselves, the name index entries
+// were stored next to them and broke ranging over either of them
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum LegacyEntry {
+    Identity(Identity),
+    NameRef { pk: Binary, value: Identity },
+}
+
+const LEGACY_ENTRIES: Map<String, LegacyEntry> = Map::new("identity");
+
+pub fn migrate(deps: DepsMut) -> Result<(), ContractError> {
+    // make sure the contract is being upgraded from the release this module expects
+    cw2::assert_contract_version(deps.storage, CONTRACT_NAME, FROM_VERSION)?;
+
+    let entries = LEGACY_ENTRIES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Drop the name index entries first, saving the identities again writes the owner, name
+    // and id type indexes to their own namespaces
+    for (key, entry) in &entries {
+        if let LegacyEntry::NameRef { .. } = entry {
+            LEGACY_ENTRIES.remove(deps.storage, key.clone());
+        }
+    }
+
+    for (_, entry) in entries {
+        if let LegacyEntry::Identity(identity) = entry {
+            identities().save(deps.storage, identity.owner.to_string(), &identity)?;
+        }
+    }
+
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, TO_VERSION)?;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use cosmwasm_std::{DepsMut, Env, Order, StdResult};
+
+use crate::contract::CONTRACT_NAME;
+use crate::state::{DaoTemplate, CONFIG, DAOS, DAO_IDS, DAO_TEMPLATES, DAO_TEMPLATE_VERSIONS};
+use crate::ContractError;
+
+// Last release without versioned DAO templates
+pub const FROM_VERSION: &str = "0.2.0";
+pub const TO_VERSION: &str = "0.3.0";
+
+pub fn migrate(deps: DepsMut, env: &Env) -> Result<(), ContractError> {
+    // make sure the contract is being upgraded from the release this module expects
+    cw2::assert_contract_version(deps.storage, CONTRACT_NAME, FROM_VERSION)?;
+
+    // The code ids could not be changed before this release, so every existing DAO was
+    // created with the configured ones
+    let mut config = CONFIG.load(deps.storage)?;
+    config.dao_template_version = 1;
+    CONFIG.save(deps.storage, &config)?;
+
+    DAO_TEMPLATES.save(
+        deps.storage,
+        config.dao_template_version,
+        &DaoTemplate {
+            version: config.dao_template_version,
+            dao_members_code_id: config.dao_members_code_id,
+            dao_multisig_code_id: config.dao_multisig_code_id,
+            added_at: env.block.time.seconds(),
+        },
+    )?;
+
+    // DAOs registered before the reverse lookup was added are only found in DAOS
+    let daos = DAOS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (dao_id, dao) in daos {
+        DAO_TEMPLATE_VERSIONS.save(deps.storage, &dao, &config.dao_template_version)?;
+        DAO_IDS.save(deps.storage, &dao, &dao_id)?;
+    }
+
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, TO_VERSION)?;
+
+    Ok(())
+}
@@ -130,6 +130,9 @@ pub struct NftInfoResponse<T> {
     pub token_uri: Option<String>,
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: T,
+    /// Frozen metadata can no longer be updated by the minter
+    #[serde(default)]
+    pub frozen: bool,
 }
 
 #[cw_serde]
@@ -25,6 +25,7 @@ pub enum ProposalQueryStatus {
     Active,
     SuccessConcluded,
     ExpiredConcluded,
+    DependencyFailedConcluded,
 }
 
 impl ProposalQueryStatus {
@@ -33,6 +34,9 @@ impl ProposalQueryStatus {
             ProposalQueryStatus::Active => "active".to_string(),
             ProposalQueryStatus::SuccessConcluded => "success_concluded".to_string(),
             ProposalQueryStatus::ExpiredConcluded => "expired_concluded".to_string(),
+            ProposalQueryStatus::DependencyFailedConcluded => {
+                "dependency_failed_concluded".to_string()
+            }
         }
     }
 }
@@ -51,6 +55,7 @@ pub enum GovernanceQueryMsg {
     },
     CoreSlots {},
     WinningGrants {},
+    PauseInfo {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
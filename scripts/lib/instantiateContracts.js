@@ -128,7 +128,7 @@ async function instantiateContracts(client, user, options = {}) {
     },
     {
       art_dealer: {
-        owner: "__governance", // __ gets hydrated with governance contract addr
+        governance: "__governance", // __ gets hydrated with governance contract addr
         identityservice_contract: "__identityservice", // __ gets hydrated with identityservice contract addr
         art_nft_name: "Art NFT",
         art_nft_symbol: "artnft",